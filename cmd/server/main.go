@@ -1,17 +1,49 @@
 package main
 
 import (
+	"context"
 	"go.keploy.io/server/server"
 	"log"
 	"net/http"
+	"os"
+	"os/signal"
+	"sync"
+	"syscall"
+	"time"
 	// "github.com/go-chi/chi"
 )
 
+// shutdownTimeout is how long in-flight requests, eg: testcases being saved, are given to
+// complete after the server is asked to stop.
+const shutdownTimeout = 30 * time.Second
+
 func main() {
-	r := server.Server()
+	background, stopBackground := context.WithCancel(context.Background())
+	var wg sync.WaitGroup
+	r := server.Server(background, &wg)
+	srv := &http.Server{Addr: ":" + "8081", Handler: r}
+
+	stop := make(chan os.Signal, 1)
+	signal.Notify(stop, os.Interrupt, syscall.SIGTERM)
+	done := make(chan struct{})
+	go func() {
+		<-stop
+		log.Printf("shutting down, waiting for in-flight requests to complete")
+		// stop the scheduled replays, which mark their unfinished test runs as failed
+		stopBackground()
+		ctx, cancel := context.WithTimeout(context.Background(), shutdownTimeout)
+		defer cancel()
+		if err := srv.Shutdown(ctx); err != nil {
+			log.Printf("failed to shut down gracefully: %v", err)
+		}
+		wg.Wait()
+		close(done)
+	}()
+
 	log.Printf("connect to http://localhost:%s/ for GraphQL playground", "8081")
-	err := http.ListenAndServe(":"+"8081", r)
-	if err != nil {
+	err := srv.ListenAndServe()
+	if err != nil && err != http.ErrServerClosed {
 		panic(err)
 	}
+	<-done
 }
@@ -18,7 +18,14 @@ import (
 func (r *mutationResolver) UpdateTestCase(ctx context.Context, tc []*model.TestCaseInput) (bool, error) {
 	var tcs []models.TestCase
 	for _, t := range tc {
-		tcs = append(tcs, ConvertTestCaseInput(t))
+		c := ConvertTestCaseInput(t)
+		// the input has no name and description, which would otherwise be cleared
+		old, err := r.reg.Get(ctx, DEFAULT_COMPANY, c.AppID, c.ID)
+		if err != nil {
+			return false, err
+		}
+		c.Name, c.Description = old.Name, old.Description
+		tcs = append(tcs, c)
 	}
 	err := r.reg.UpdateTC(ctx, tcs)
 	if err != nil {
@@ -38,7 +45,7 @@ func (r *mutationResolver) DeleteTestCase(ctx context.Context, id string) (bool,
 func (r *mutationResolver) NormalizeTests(ctx context.Context, ids []string) (bool, error) {
 	var errStrings []string
 	for _, id := range ids {
-		err := r.run.Normalize(ctx, DEFAULT_COMPANY, id)
+		_, err := r.run.Normalize(ctx, DEFAULT_COMPANY, id, nil, false)
 		if err != nil {
 			errStrings = append(errStrings, id+": "+err.Error())
 		}
@@ -180,7 +187,7 @@ type subscriptionResolver struct{ *Resolver }
 //    it when you're done.
 //  - You have helper methods in this file. Move them out to keep these resolver files clean.
 func (r *mutationResolver) NormalizeTest(ctx context.Context, id string) (bool, error) {
-	err := r.run.Normalize(ctx, DEFAULT_COMPANY, id)
+	_, err := r.run.Normalize(ctx, DEFAULT_COMPANY, id, nil, false)
 	if err != nil {
 		return false, err
 	}
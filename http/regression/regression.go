@@ -1,8 +1,11 @@
 package regression
 
 import (
+	"bytes"
+	"context"
 	"errors"
 	// "fmt"
+	"io/ioutil"
 	"net/http"
 	"strconv"
 	"time"
@@ -11,12 +14,25 @@ import (
 	"github.com/go-chi/render"
 	"github.com/google/uuid"
 	"go.keploy.io/server/graph"
+	"go.keploy.io/server/pkg/bundle"
 	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/platform/wiremock"
 	regression2 "go.keploy.io/server/pkg/service/regression"
 	"go.keploy.io/server/pkg/service/run"
 	"go.uber.org/zap"
 )
 
+const (
+	// TestNameHeader and TestDescriptionHeader can be set by the application on a response to
+	// name and describe the testcase recorded from it.
+	TestNameHeader        = models.TestNameHeader
+	TestDescriptionHeader = models.TestDescriptionHeader
+
+	// BundlePassphraseHeader sets the passphrase with which bundles are encrypted on export and
+	// decrypted on import. It is a header so that it doesn't end up in access logs.
+	BundlePassphraseHeader = "X-Keploy-Bundle-Passphrase"
+)
+
 func New(r chi.Router, logger *zap.Logger, svc regression2.Service, run run.Service) {
 	s := &regression{logger: logger, svc: svc, run: run}
 
@@ -25,9 +41,24 @@ func New(r chi.Router, logger *zap.Logger, svc regression2.Service, run run.Serv
 			r.Get("/{id}", s.GetTC)
 			r.Get("/", s.GetTCS)
 			r.Post("/", s.PostTC)
+			r.Put("/", s.PutTC)
+			r.Post("/manual", s.PostManualTC)
 		})
 		r.Post("/test", s.Test)
+		r.Post("/replay", s.Replay)
 		r.Post("/denoise", s.DeNoise)
+		r.Post("/normalize", s.Normalize)
+		r.Get("/drift", s.Drift)
+		r.Get("/wiremock", s.WireMock)
+		r.Route("/bundle", func(r chi.Router) {
+			r.Get("/", s.ExportBundle)
+			r.Post("/", s.ImportBundle)
+		})
+		r.Route("/filter", func(r chi.Router) {
+			r.Get("/", s.GetFilter)
+			r.Put("/", s.PutFilter)
+		})
+		r.Get("/status", s.Status)
 		r.Get("/start", s.Start)
 		r.Get("/end", s.End)
 
@@ -60,10 +91,44 @@ func (rg *regression) End(w http.ResponseWriter, r *http.Request) {
 		render.Render(w, r, ErrInvalidRequest(err))
 		return
 	}
+	trs, err := rg.run.Get(r.Context(), true, graph.DEFAULT_COMPANY, nil, nil, &id, nil, nil, nil, nil)
+	if err == nil && len(trs) > 0 {
+		tr := trs[0]
+		rg.logger.Info("test run completed", zap.String("test run", tr.ID), zap.String("appID", tr.App), zap.String("status", string(tr.Status)),
+			zap.Int("success", tr.Success), zap.Int("failure", tr.Failure), zap.Int("total", tr.Total))
+	}
 	render.Status(r, http.StatusOK)
 
 }
 
+// Status reports the configuration of the server, the number of testcases of every app and the
+// test runs which are still running among the recent ones.
+func (rg *regression) Status(w http.ResponseWriter, r *http.Request) {
+	status, err := rg.svc.Status(r.Context(), graph.DEFAULT_COMPANY)
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	trs, err := rg.run.Get(r.Context(), true, graph.DEFAULT_COMPANY, nil, nil, nil, nil, nil, nil, nil)
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	running := []*run.TestRun{}
+	for _, tr := range trs {
+		if tr.Status == run.TestRunStatusRunning {
+			running = append(running, tr)
+		}
+	}
+
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, map[string]interface{}{
+		"status":  status,
+		"running": running,
+	})
+
+}
+
 func (rg *regression) Start(w http.ResponseWriter, r *http.Request) {
 	t := r.URL.Query().Get("total")
 	total, err := strconv.Atoi(t)
@@ -206,6 +271,81 @@ func (rg *regression) PostTC(w http.ResponseWriter, r *http.Request) {
 
 }
 
+// PostManualTC adds a hand-written testcase, which is tested along with the recorded ones.
+func (rg *regression) PostManualTC(w http.ResponseWriter, r *http.Request) {
+	data := &ManualTestCaseReq{}
+	if err := render.Bind(r, data); err != nil {
+		rg.logger.Error("error parsing request", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	tc, err := data.TestCase(uuid.New().String(), time.Now().UTC().Unix())
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	ids, err := rg.svc.Import(r.Context(), graph.DEFAULT_COMPANY, data.AppID, []models.TestCase{tc})
+	if err != nil {
+		rg.logger.Error("error putting testcase", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, map[string]string{"id": ids[0]})
+
+}
+
+// PutTC updates the http request, response, noise fields and transforms of existing testcases.
+// Their name and description are replaced too, so omitting them clears them.
+func (rg *regression) PutTC(w http.ResponseWriter, r *http.Request) {
+	var tcs []models.TestCase
+	if err := render.DecodeJSON(r.Body, &tcs); err != nil {
+		rg.logger.Error("error parsing request", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	for _, tc := range tcs {
+		if tc.ID == "" {
+			render.Render(w, r, ErrInvalidRequest(errors.New("id is required")))
+			return
+		}
+	}
+
+	err := rg.svc.UpdateTC(r.Context(), tcs)
+	if err != nil {
+		rg.logger.Error("error updating testcases", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+
+	render.Status(r, http.StatusOK)
+
+}
+
+// Replay tests the testcases of an app against a remote deployment of the app in a new test
+// run, without the app being instrumented by the SDK.
+func (rg *regression) Replay(w http.ResponseWriter, r *http.Request) {
+	data := &ReplayReq{}
+	if err := render.Bind(r, data); err != nil {
+		rg.logger.Error("error parsing request", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+
+	// the test run is finished even if the client stops waiting for it
+	tr, err := rg.svc.Replay(context.Background(), graph.DEFAULT_COMPANY, graph.DEFAULT_USER, data.AppID, data.BaseURL, data.Concurrency, data.Auth)
+	if err != nil {
+		rg.logger.Error("error replaying testcases", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, tr)
+
+}
+
 func (rg *regression) DeNoise(w http.ResponseWriter, r *http.Request) {
 	// key := r.Header.Get("key")
 	// if key == "" {
@@ -232,6 +372,163 @@ func (rg *regression) DeNoise(w http.ResponseWriter, r *http.Request) {
 
 }
 
+// Normalize updates the testcases of the given tests with the responses from the tests. Only
+// the given fields are updated if any and with dry_run the updated responses are returned
+// without saving them, so that they can be reviewed first.
+func (rg *regression) Normalize(w http.ResponseWriter, r *http.Request) {
+	data := &NormalizeReq{}
+	if err := render.Bind(r, data); err != nil {
+		rg.logger.Error("error parsing request", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+
+	var res []run.NormalizeResult
+	for _, id := range data.IDs {
+		nr, err := rg.run.Normalize(r.Context(), graph.DEFAULT_COMPANY, id, data.Fields, data.DryRun)
+		if err != nil {
+			rg.logger.Error("error normalizing testcase", zap.Error(err))
+			render.Render(w, r, ErrInvalidRequest(err))
+			return
+		}
+		res = append(res, nr)
+	}
+
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, res)
+
+}
+
+// Drift returns how the responses of every endpoint changed in the given test run, to help
+// decide between fixing the application and re-recording the testcases.
+func (rg *regression) Drift(w http.ResponseWriter, r *http.Request) {
+	id := r.URL.Query().Get("id")
+	if id == "" {
+		render.Render(w, r, ErrInvalidRequest(errors.New("missing test run id")))
+		return
+	}
+	res, err := rg.run.Drift(r.Context(), graph.DEFAULT_COMPANY, id)
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, res)
+
+}
+
+// WireMock exports all the testcases of an app as WireMock stub mappings, which consumer
+// teams can load into a WireMock server in their own CI.
+func (rg *regression) WireMock(w http.ResponseWriter, r *http.Request) {
+	app := rg.getMeta(w, r, true)
+	if app == "" {
+		return
+	}
+	// a limit of 0 fetches every testcase
+	offset, limit := 0, 0
+	tcs, err := rg.svc.GetAll(r.Context(), graph.DEFAULT_COMPANY, app, &offset, &limit)
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, wiremock.FromTestCases(tcs))
+
+}
+
+// ExportBundle writes all the testcases of an app as a single bundle file. The bundle is
+// encrypted if a passphrase is given in the BundlePassphraseHeader.
+func (rg *regression) ExportBundle(w http.ResponseWriter, r *http.Request) {
+	app := rg.getMeta(w, r, true)
+	if app == "" {
+		return
+	}
+	tcs, err := rg.svc.Export(r.Context(), graph.DEFAULT_COMPANY, app)
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	passphrase := r.Header.Get(BundlePassphraseHeader)
+	if passphrase == "" {
+		w.Header().Set("Content-Type", "application/gzip")
+		w.Header().Set("Content-Disposition", "attachment; filename=\""+app+".kbundle\"")
+		if err = bundle.Write(w, app, tcs); err != nil {
+			rg.logger.Error("error writing bundle", zap.Error(err))
+		}
+		return
+	}
+	var buf bytes.Buffer
+	if err = bundle.Write(&buf, app, tcs); err != nil {
+		rg.logger.Error("error writing bundle", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	w.Header().Set("Content-Type", "application/octet-stream")
+	w.Header().Set("Content-Disposition", "attachment; filename=\""+app+".kbundle\"")
+	if err = bundle.Encrypt(w, buf.Bytes(), passphrase); err != nil {
+		rg.logger.Error("error encrypting bundle", zap.Error(err))
+	}
+
+}
+
+// ImportBundle saves the testcases of a bundle file. They are saved under the app of the
+// bundle unless another app is given. Encrypted bundles need the passphrase they were exported
+// with.
+func (rg *regression) ImportBundle(w http.ResponseWriter, r *http.Request) {
+	app := rg.getMeta(w, r, false)
+	b, err := ioutil.ReadAll(http.MaxBytesReader(w, r.Body, bundle.MaxSize))
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	if bundle.Encrypted(b) {
+		b, err = bundle.Decrypt(b, r.Header.Get(BundlePassphraseHeader))
+		if err != nil {
+			render.Render(w, r, ErrInvalidRequest(err))
+			return
+		}
+	}
+	m, tcs, err := bundle.Read(bytes.NewReader(b))
+	if err != nil {
+		rg.logger.Error("error reading bundle", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	if app == "" {
+		app = m.App
+	}
+	ids, err := rg.svc.Import(r.Context(), graph.DEFAULT_COMPANY, app, tcs)
+	if err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, map[string][]string{"ids": ids})
+
+}
+
+func (rg *regression) GetFilter(w http.ResponseWriter, r *http.Request) {
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, rg.svc.GetFilter(r.Context()))
+}
+
+// PutFilter replaces the record filters of a running server, so that long recording sessions
+// don't have to be restarted to change them.
+func (rg *regression) PutFilter(w http.ResponseWriter, r *http.Request) {
+	var f models.Filter
+	if err := render.DecodeJSON(r.Body, &f); err != nil {
+		rg.logger.Error("error parsing request", zap.Error(err))
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	if err := rg.svc.SetFilter(r.Context(), f); err != nil {
+		render.Render(w, r, ErrInvalidRequest(err))
+		return
+	}
+	render.Status(r, http.StatusOK)
+	render.JSON(w, r, f)
+}
+
 func (rg *regression) Test(w http.ResponseWriter, r *http.Request) {
 
 	data := &TestReq{}
@@ -0,0 +1,130 @@
+package regression
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/go-chi/chi"
+	"go.keploy.io/server/pkg/models"
+	regression2 "go.keploy.io/server/pkg/service/regression"
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+// mockTestCaseDB counts the testcases of the given apps; its other methods aren't used.
+type mockTestCaseDB struct {
+	models.TestCaseDB
+	counts map[string]int64
+	apps   []string
+}
+
+func (m *mockTestCaseDB) GetApps(ctx context.Context, cid string) ([]string, error) {
+	return m.apps, nil
+}
+
+func (m *mockTestCaseDB) Count(ctx context.Context, cid, app string) (int64, error) {
+	return m.counts[app], nil
+}
+
+// mockRunService returns the given test runs; its other methods aren't used.
+type mockRunService struct {
+	run.Service
+	runs []*run.TestRun
+}
+
+func (m *mockRunService) Get(ctx context.Context, summary bool, cid string, user, app, id *string, from, to *time.Time, offset *int, limit *int) ([]*run.TestRun, error) {
+	return m.runs, nil
+}
+
+func newTestRouter(tdb models.TestCaseDB, rs run.Service, conf regression2.Config) *chi.Mux {
+	r := chi.NewRouter()
+	New(r, zap.NewNop(), regression2.New(tdb, nil, zap.NewNop(), conf, nil, http.Client{}), rs)
+	return r
+}
+
+func serve(h http.Handler, method, target, body string) *httptest.ResponseRecorder {
+	w := httptest.NewRecorder()
+	h.ServeHTTP(w, httptest.NewRequest(method, target, strings.NewReader(body)))
+	return w
+}
+
+func TestStatus(t *testing.T) {
+	tdb := &mockTestCaseDB{apps: []string{"users", "orders"}, counts: map[string]int64{"users": 3, "orders": 0}}
+	rs := &mockRunService{runs: []*run.TestRun{
+		{ID: "1", App: "users", Status: run.TestRunStatusPassed},
+		{ID: "2", App: "users", Status: run.TestRunStatusRunning},
+		{ID: "3", App: "orders", Status: run.TestRunStatusFailed},
+	}}
+	router := newTestRouter(tdb, rs, regression2.Config{EnableDeDup: true, FailOnStale: true})
+
+	w := serve(router, http.MethodGet, "/regression/status", "")
+	if w.Code != http.StatusOK {
+		t.Fatal("THIS IS EXP", http.StatusOK, " \n THIS IS ACT", w.Code, w.Body.String())
+	}
+	var res struct {
+		Status  regression2.Status `json:"status"`
+		Running []*run.TestRun     `json:"running"`
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil {
+		t.Fatal(err)
+	}
+	exp := regression2.Status{
+		EnableDeDup: true,
+		FailOnStale: true,
+		Apps:        []regression2.AppStatus{{App: "users", TestCases: 3}, {App: "orders", TestCases: 0}},
+	}
+	if !reflect.DeepEqual(res.Status, exp) {
+		t.Fatal("THIS IS EXP", exp, " \n THIS IS ACT", res.Status)
+	}
+	if len(res.Running) != 1 || res.Running[0].ID != "2" {
+		t.Fatal("THIS IS EXP", "test run 2", " \n THIS IS ACT", res.Running)
+	}
+}
+
+func TestFilter(t *testing.T) {
+	router := newTestRouter(&mockTestCaseDB{}, &mockRunService{}, regression2.Config{})
+	for _, tt := range []struct {
+		body   string
+		code   int
+		filter models.Filter
+	}{
+		{
+			body:   `{"exclude_paths": ["^/health"], "methods": ["GET"], "exclude_status": ["5xx"]}`,
+			code:   http.StatusOK,
+			filter: models.Filter{ExcludePaths: []string{"^/health"}, Methods: []models.Method{models.MethodGet}, ExcludeStatus: []string{"5xx"}},
+		},
+		// invalid filters don't replace the current ones
+		{
+			body:   `{"include_paths": ["("]}`,
+			code:   http.StatusBadRequest,
+			filter: models.Filter{ExcludePaths: []string{"^/health"}, Methods: []models.Method{models.MethodGet}, ExcludeStatus: []string{"5xx"}},
+		},
+		{
+			body:   `{"exclude_status": ["600-"]}`,
+			code:   http.StatusBadRequest,
+			filter: models.Filter{ExcludePaths: []string{"^/health"}, Methods: []models.Method{models.MethodGet}, ExcludeStatus: []string{"5xx"}},
+		},
+		{
+			body: `{}`,
+			code: http.StatusOK,
+		},
+	} {
+		if w := serve(router, http.MethodPut, "/regression/filter", tt.body); w.Code != tt.code {
+			t.Fatal(tt.body, "THIS IS EXP", tt.code, " \n THIS IS ACT", w.Code, w.Body.String())
+		}
+		w := serve(router, http.MethodGet, "/regression/filter", "")
+		var res models.Filter
+		if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil {
+			t.Fatal(err)
+		}
+		if !reflect.DeepEqual(res, tt.filter) {
+			t.Fatal(tt.body, "THIS IS EXP", tt.filter, " \n THIS IS ACT", res)
+		}
+	}
+}
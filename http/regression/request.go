@@ -1,10 +1,14 @@
 package regression
 
 import (
+	"encoding/json"
 	"errors"
 	"net/http"
+	"net/url"
+	"strings"
 
 	"go.keploy.io/server/pkg/models"
+	regression2 "go.keploy.io/server/pkg/service/regression"
 )
 
 type TestCaseReq struct {
@@ -46,3 +50,131 @@ func (req *TestReq) Bind(r *http.Request) error {
 
 	return nil
 }
+
+type NormalizeReq struct {
+	IDs    []string `json:"ids"`
+	Fields []string `json:"fields"`
+	DryRun bool     `json:"dry_run"`
+}
+
+func (req *NormalizeReq) Bind(r *http.Request) error {
+	if len(req.IDs) == 0 {
+		return errors.New("ids are required")
+	}
+	return nil
+}
+
+type ReplayReq struct {
+	AppID       string                 `json:"app_id"`
+	BaseURL     string                 `json:"base_url"`
+	Concurrency int                    `json:"concurrency"`
+	Auth        *regression2.TokenAuth `json:"auth"`
+}
+
+func (req *ReplayReq) Bind(r *http.Request) error {
+	if req.AppID == "" {
+		return errors.New("app id is required")
+	}
+	if req.BaseURL == "" {
+		return errors.New("base url is required")
+	}
+	return nil
+}
+
+// ManualTestCaseReq is a hand-written testcase for a request which can't be recorded.
+// Headers only have a single value and bodies can be given as JSON values instead of strings.
+type ManualTestCaseReq struct {
+	AppID       string            `json:"app_id"`
+	Name        string            `json:"name"`
+	Description string            `json:"description"`
+	Method      models.Method     `json:"method"`
+	URL         string            `json:"url"`
+	Header      map[string]string `json:"header"`
+	Body        json.RawMessage   `json:"body"`
+	Expect      ManualExpect      `json:"expect"`
+	Noise       []string          `json:"noise"`
+}
+
+type ManualExpect struct {
+	StatusCode int               `json:"status_code"`
+	Header     map[string]string `json:"header"`
+	Body       json.RawMessage   `json:"body"`
+}
+
+func (req *ManualTestCaseReq) Bind(r *http.Request) error {
+	if req.AppID == "" {
+		return errors.New("app id is required")
+	}
+	if req.Method == "" || req.URL == "" {
+		return errors.New("method and url are required")
+	}
+	if req.Expect.StatusCode == 0 {
+		return errors.New("expected status code is required")
+	}
+	return nil
+}
+
+// TestCase compiles the hand-written testcase into a testcase.
+func (req *ManualTestCaseReq) TestCase(id string, now int64) (models.TestCase, error) {
+	u, err := url.Parse(req.URL)
+	if err != nil {
+		return models.TestCase{}, err
+	}
+	reqBody, err := manualBody(req.Body)
+	if err != nil {
+		return models.TestCase{}, err
+	}
+	respBody, err := manualBody(req.Expect.Body)
+	if err != nil {
+		return models.TestCase{}, err
+	}
+	return models.TestCase{
+		ID:       id,
+		Created:  now,
+		Updated:  now,
+		Captured: now,
+		AppID:    req.AppID,
+		URI:      u.Path,
+		HttpReq: models.HttpReq{
+			Method:     models.Method(strings.ToUpper(string(req.Method))),
+			ProtoMajor: 1,
+			ProtoMinor: 1,
+			URL:        req.URL,
+			Header:     manualHeader(req.Header),
+			Body:       reqBody,
+		},
+		HttpResp: models.HttpResp{
+			StatusCode: req.Expect.StatusCode,
+			Header:     manualHeader(req.Expect.Header),
+			Body:       respBody,
+		},
+		Noise:       req.Noise,
+		Name:        req.Name,
+		Description: req.Description,
+	}, nil
+}
+
+func manualHeader(h map[string]string) http.Header {
+	res := http.Header{}
+	for k, v := range h {
+		res.Set(k, v)
+	}
+	return res
+}
+
+// manualBody returns JSON strings as they are and other JSON values compacted.
+func manualBody(b json.RawMessage) (string, error) {
+	if len(b) == 0 || string(b) == "null" {
+		return "", nil
+	}
+	var s string
+	if err := json.Unmarshal(b, &s); err == nil {
+		return s, nil
+	}
+	var v interface{}
+	if err := json.Unmarshal(b, &v); err != nil {
+		return "", err
+	}
+	res, err := json.Marshal(v)
+	return string(res), err
+}
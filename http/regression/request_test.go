@@ -0,0 +1,79 @@
+package regression
+
+import (
+	"encoding/json"
+	"net/http"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+func TestManualTestCase(t *testing.T) {
+	for _, tt := range []struct {
+		req      ManualTestCaseReq
+		uri      string
+		method   models.Method
+		reqBody  string
+		respBody string
+		err      bool
+	}{
+		// JSON bodies are compacted, string bodies are used as they are
+		{
+			req: ManualTestCaseReq{
+				AppID:  "app",
+				Method: "post",
+				URL:    "http://localhost:8080/users?admin=true",
+				Header: map[string]string{"content-type": "application/json"},
+				Body:   json.RawMessage(`{ "name": "alice",  "roles": ["admin"] }`),
+				Expect: ManualExpect{StatusCode: 201, Body: json.RawMessage(`"created"`)},
+			},
+			uri:      "/users",
+			method:   models.MethodPost,
+			reqBody:  `{"name":"alice","roles":["admin"]}`,
+			respBody: "created",
+		},
+		{
+			req: ManualTestCaseReq{
+				AppID:  "app",
+				Method: "Get",
+				URL:    "http://localhost:8080/health",
+				Expect: ManualExpect{StatusCode: 200, Body: json.RawMessage(`null`)},
+			},
+			uri:    "/health",
+			method: models.MethodGet,
+		},
+		{
+			req: ManualTestCaseReq{AppID: "app", Method: "GET", URL: "://localhost", Expect: ManualExpect{StatusCode: 200}},
+			err: true,
+		},
+		{
+			req: ManualTestCaseReq{AppID: "app", Method: "POST", URL: "http://localhost:8080/users", Body: json.RawMessage(`{"name":`), Expect: ManualExpect{StatusCode: 201}},
+			err: true,
+		},
+	} {
+		tc, err := tt.req.TestCase("1", 10)
+		if (err != nil) != tt.err {
+			t.Fatal(tt.req, "THIS IS EXP", tt.err, " \n THIS IS ACT", err)
+		}
+		if err != nil {
+			continue
+		}
+		if tc.URI != tt.uri || tc.HttpReq.Method != tt.method || tc.HttpReq.URL != tt.req.URL {
+			t.Fatal("THIS IS EXP", tt.method, tt.uri, " \n THIS IS ACT", tc.HttpReq.Method, tc.URI)
+		}
+		if tc.HttpReq.Body != tt.reqBody || tc.HttpResp.Body != tt.respBody {
+			t.Fatal("THIS IS EXP", tt.reqBody, tt.respBody, " \n THIS IS ACT", tc.HttpReq.Body, tc.HttpResp.Body)
+		}
+		if tc.HttpResp.StatusCode != tt.req.Expect.StatusCode || tc.Captured != 10 {
+			t.Fatal("THIS IS EXP", tt.req.Expect.StatusCode, " \n THIS IS ACT", tc.HttpResp)
+		}
+		for k, v := range tt.req.Header {
+			if got := tc.HttpReq.Header.Get(k); got != v {
+				t.Fatal("THIS IS EXP", v, " \n THIS IS ACT", got)
+			}
+			if _, ok := tc.HttpReq.Header[http.CanonicalHeaderKey(k)]; !ok {
+				t.Fatal("expected canonical header keys, got", tc.HttpReq.Header)
+			}
+		}
+	}
+}
@@ -0,0 +1,80 @@
+package pkg
+
+import (
+	"fmt"
+	"net/http"
+	"regexp"
+	"strings"
+
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+)
+
+// ValidateHeaderAssertions returns an error for the first invalid header assertion.
+func ValidateHeaderAssertions(rules []models.HeaderAssertion) error {
+	for _, a := range rules {
+		if strings.TrimSpace(a.Header) == "" {
+			return fmt.Errorf("invalid header assertion %q: missing header name", a.Rule)
+		}
+		switch a.Rule {
+		case models.AssertExact, models.AssertExists, models.AssertAbsent:
+		case models.AssertRegex:
+			if _, err := regexp.Compile(a.Value); err != nil {
+				return fmt.Errorf("invalid header assertion %q on %q: %w", a.Rule, a.Header, err)
+			}
+		default:
+			return fmt.Errorf("invalid header assertion %q on %q: unknown rule", a.Rule, a.Header)
+		}
+	}
+	return nil
+}
+
+// AssertHeaders checks the actual headers against the assertions and appends a result for every
+// assertion to res. It returns both the headers without the asserted ones, which are then
+// compared as usual.
+func AssertHeaders(rules []models.HeaderAssertion, exp, act http.Header, res *[]run.HeaderResult) (http.Header, http.Header, bool) {
+	if len(rules) == 0 {
+		return exp, act, true
+	}
+	exp, act = exp.Clone(), act.Clone()
+	pass := true
+	for _, a := range rules {
+		key := http.CanonicalHeaderKey(a.Header)
+		val, ok := act[key]
+		delete(exp, key)
+		delete(act, key)
+		var normal bool
+		switch a.Rule {
+		case models.AssertExact:
+			normal = len(val) == 1 && val[0] == a.Value
+		case models.AssertRegex:
+			re, err := regexp.Compile(a.Value)
+			normal = err == nil && ok
+			for _, v := range val {
+				if normal && !re.MatchString(v) {
+					normal = false
+				}
+			}
+		case models.AssertExists:
+			normal = ok
+		case models.AssertAbsent:
+			normal = !ok
+		}
+		if !normal {
+			pass = false
+		}
+		expected := run.Header{Key: key}
+		if a.Rule == models.AssertExact || a.Rule == models.AssertRegex {
+			expected.Value = []string{a.Value}
+		}
+		*res = append(*res, run.HeaderResult{
+			Normal:   normal,
+			Expected: expected,
+			Actual: run.Header{
+				Key:   key,
+				Value: val,
+			},
+		})
+	}
+	return exp, act, pass
+}
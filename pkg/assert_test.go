@@ -0,0 +1,97 @@
+package pkg
+
+import (
+	"net/http"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+)
+
+func TestAssertHeaders(t *testing.T) {
+	exp := http.Header{
+		"Location":      {"/users/1"},
+		"Cache-Control": {"no-store"},
+		"Content-Type":  {"application/json"},
+	}
+	for _, tt := range []struct {
+		rules  []models.HeaderAssertion
+		actual http.Header
+		result bool
+	}{
+		{
+			rules: []models.HeaderAssertion{{Header: "location", Rule: models.AssertRegex, Value: `^/users/\d+$`}},
+			actual: http.Header{
+				"Location":      {"/users/2"},
+				"Cache-Control": {"no-store"},
+				"Content-Type":  {"application/json"},
+			},
+			result: true,
+		},
+		{
+			rules: []models.HeaderAssertion{{Header: "Location", Rule: models.AssertRegex, Value: `^/users/\d+$`}},
+			actual: http.Header{
+				"Location":      {"/login"},
+				"Cache-Control": {"no-store"},
+				"Content-Type":  {"application/json"},
+			},
+			result: false,
+		},
+		// the headers without assertions are still compared
+		{
+			rules: []models.HeaderAssertion{{Header: "Location", Rule: models.AssertExists}},
+			actual: http.Header{
+				"Location":      {"/users/2"},
+				"Cache-Control": {"max-age=60"},
+				"Content-Type":  {"application/json"},
+			},
+			result: false,
+		},
+		{
+			rules: []models.HeaderAssertion{
+				{Header: "Cache-Control", Rule: models.AssertExact, Value: "no-store"},
+				{Header: "Location", Rule: models.AssertExists},
+				{Header: "Set-Cookie", Rule: models.AssertAbsent},
+			},
+			actual: http.Header{
+				"Location":      {"/users/2"},
+				"Cache-Control": {"no-store"},
+				"Content-Type":  {"application/json"},
+			},
+			result: true,
+		},
+		{
+			rules: []models.HeaderAssertion{{Header: "Set-Cookie", Rule: models.AssertAbsent}},
+			actual: http.Header{
+				"Location":      {"/users/1"},
+				"Cache-Control": {"no-store"},
+				"Content-Type":  {"application/json"},
+				"Set-Cookie":    {"session=abc"},
+			},
+			result: false,
+		},
+	} {
+		hRes := &[]run.HeaderResult{}
+		expRest, actRest, pass := AssertHeaders(tt.rules, exp, tt.actual, hRes)
+		if res := CompareHeaders(expRest, actRest, hRes) && pass; res != tt.result {
+			t.Fatal(tt.rules, tt.actual, "THIS IS EXP", tt.result, " \n THIS IS ACT", res, *hRes)
+		}
+		if tt.result {
+			if len(*hRes) < len(tt.rules) {
+				t.Fatal("expected a result for every assertion", "THIS IS EXP", len(tt.rules), " \n THIS IS ACT", *hRes)
+			}
+			for _, h := range *hRes {
+				if !h.Normal {
+					t.Fatal("expected only passing results", *hRes)
+				}
+			}
+		}
+		if _, ok := tt.actual["Location"]; !ok {
+			t.Fatal("expected the actual headers not to be modified")
+		}
+	}
+
+	if err := ValidateHeaderAssertions([]models.HeaderAssertion{{Header: "Location", Rule: models.AssertRegex, Value: "("}}); err == nil {
+		t.Fatal("expected an error for an invalid regular expression")
+	}
+}
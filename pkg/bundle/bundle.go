@@ -0,0 +1,165 @@
+package bundle
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"io"
+	"io/ioutil"
+	"time"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+// Version is the version of the bundle format written by Write.
+const Version = 1
+
+const manifestFile = "manifest.json"
+
+// Limits on the bundles read by Read, so that a small compressed upload can't exhaust the
+// memory of the server.
+const (
+	// MaxSize is the maximum size of the uncompressed files of a bundle.
+	MaxSize     = 256 << 20
+	maxFiles    = 100000
+	maxFileSize = 16 << 20
+)
+
+// Manifest describes the contents of a bundle. It is the first file of the archive.
+type Manifest struct {
+	Version   int    `json:"version"`
+	App       string `json:"app"`
+	Created   int64  `json:"created"`
+	TestCases []File `json:"testcases"`
+}
+
+type File struct {
+	Name   string `json:"name"`
+	SHA256 string `json:"sha256"`
+}
+
+// Write writes the testcases of an app as a single gzipped tar archive, so that they can be
+// attached to bug reports or moved to another keploy server as one file.
+func Write(w io.Writer, app string, tcs []models.TestCase) error {
+	m := Manifest{Version: Version, App: app, Created: time.Now().Unix()}
+	files := make([][]byte, len(tcs))
+	for i, tc := range tcs {
+		b, err := json.MarshalIndent(tc, "", "  ")
+		if err != nil {
+			return err
+		}
+		files[i] = b
+		m.TestCases = append(m.TestCases, File{Name: "testcases/" + tc.ID + ".json", SHA256: checksum(b)})
+	}
+	mb, err := json.MarshalIndent(m, "", "  ")
+	if err != nil {
+		return err
+	}
+
+	gw := gzip.NewWriter(w)
+	tw := tar.NewWriter(gw)
+	if err = writeFile(tw, manifestFile, mb); err != nil {
+		return err
+	}
+	for i, f := range m.TestCases {
+		if err = writeFile(tw, f.Name, files[i]); err != nil {
+			return err
+		}
+	}
+	if err = tw.Close(); err != nil {
+		return err
+	}
+	return gw.Close()
+}
+
+// Read reads a bundle written by Write. It returns an error if a testcase listed in the
+// manifest is missing or doesn't match its checksum.
+func Read(r io.Reader) (Manifest, []models.TestCase, error) {
+	var m Manifest
+	gr, err := gzip.NewReader(r)
+	if err != nil {
+		return m, nil, err
+	}
+	defer gr.Close()
+	tr := tar.NewReader(gr)
+	files := map[string][]byte{}
+	entries, total := 0, 0
+	for {
+		h, err := tr.Next()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return m, nil, err
+		}
+		// entries with the same name are counted too
+		entries++
+		if entries > maxFiles {
+			return m, nil, fmt.Errorf("invalid bundle: more than %d files", maxFiles)
+		}
+		if h.Size > maxFileSize {
+			return m, nil, fmt.Errorf("invalid bundle: %s is larger than %d bytes", h.Name, maxFileSize)
+		}
+		b, err := ioutil.ReadAll(io.LimitReader(tr, maxFileSize+1))
+		if err != nil {
+			return m, nil, err
+		}
+		if len(b) > maxFileSize {
+			return m, nil, fmt.Errorf("invalid bundle: %s is larger than %d bytes", h.Name, maxFileSize)
+		}
+		total += len(b)
+		if total > MaxSize {
+			return m, nil, fmt.Errorf("invalid bundle: larger than %d bytes", MaxSize)
+		}
+		files[h.Name] = b
+	}
+
+	mb, ok := files[manifestFile]
+	if !ok {
+		return m, nil, fmt.Errorf("invalid bundle: missing %s", manifestFile)
+	}
+	if err = json.Unmarshal(mb, &m); err != nil {
+		return m, nil, fmt.Errorf("invalid bundle manifest: %w", err)
+	}
+	if m.Version > Version {
+		return m, nil, fmt.Errorf("unsupported bundle version %d", m.Version)
+	}
+	var tcs []models.TestCase
+	for _, f := range m.TestCases {
+		b, ok := files[f.Name]
+		if !ok {
+			return m, nil, fmt.Errorf("invalid bundle: missing %s", f.Name)
+		}
+		if checksum(b) != f.SHA256 {
+			return m, nil, fmt.Errorf("invalid bundle: checksum mismatch for %s", f.Name)
+		}
+		var tc models.TestCase
+		if err = json.Unmarshal(b, &tc); err != nil {
+			return m, nil, fmt.Errorf("invalid bundle: %s: %w", f.Name, err)
+		}
+		tcs = append(tcs, tc)
+	}
+	return m, tcs, nil
+}
+
+func writeFile(tw *tar.Writer, name string, b []byte) error {
+	err := tw.WriteHeader(&tar.Header{
+		Name:    name,
+		Mode:    0644,
+		Size:    int64(len(b)),
+		ModTime: time.Now(),
+	})
+	if err != nil {
+		return err
+	}
+	_, err = tw.Write(b)
+	return err
+}
+
+func checksum(b []byte) string {
+	sum := sha256.Sum256(b)
+	return hex.EncodeToString(sum[:])
+}
@@ -0,0 +1,89 @@
+package bundle
+
+import (
+	"archive/tar"
+	"bytes"
+	"compress/gzip"
+	"io"
+	"io/ioutil"
+	"strings"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+func TestBundle(t *testing.T) {
+	tcs := []models.TestCase{
+		{ID: "1", AppID: "app", URI: "/users", HttpReq: models.HttpReq{Method: models.MethodGet}, HttpResp: models.HttpResp{StatusCode: 200, Body: `{"id": 1}`}},
+		{ID: "2", AppID: "app", URI: "/users", HttpReq: models.HttpReq{Method: models.MethodPost}, HttpResp: models.HttpResp{StatusCode: 201}},
+	}
+	var buf bytes.Buffer
+	if err := Write(&buf, "app", tcs); err != nil {
+		t.Fatal(err)
+	}
+	m, res, err := Read(bytes.NewReader(buf.Bytes()))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if m.App != "app" || len(res) != 2 || res[0].ID != "1" || res[1].HttpResp.StatusCode != 201 {
+		t.Fatal("THIS IS EXP", tcs, " \n THIS IS ACT", res)
+	}
+
+	// a testcase edited after the export
+	var tampered bytes.Buffer
+	gr, _ := gzip.NewReader(bytes.NewReader(buf.Bytes()))
+	tr := tar.NewReader(gr)
+	gw := gzip.NewWriter(&tampered)
+	tw := tar.NewWriter(gw)
+	for {
+		h, err := tr.Next()
+		if err == io.EOF {
+			break
+		}
+		b, _ := ioutil.ReadAll(tr)
+		if h.Name == "testcases/1.json" {
+			b = bytes.Replace(b, []byte("200"), []byte("500"), 1)
+		}
+		h.Size = int64(len(b))
+		tw.WriteHeader(h)
+		tw.Write(b)
+	}
+	tw.Close()
+	gw.Close()
+	if _, _, err = Read(&tampered); err == nil {
+		t.Fatal("expected a checksum mismatch")
+	}
+}
+
+func TestBundleLimits(t *testing.T) {
+	// a small upload which decompresses to a file larger than the limit
+	var buf bytes.Buffer
+	gw := gzip.NewWriter(&buf)
+	tw := tar.NewWriter(gw)
+	tw.WriteHeader(&tar.Header{Name: manifestFile, Mode: 0644, Size: maxFileSize + 1})
+	tw.Write(make([]byte, maxFileSize+1))
+	tw.Close()
+	gw.Close()
+	if buf.Len() > maxFileSize/100 {
+		t.Fatal("expected the bundle to compress well, got", buf.Len())
+	}
+	if _, _, err := Read(&buf); err == nil {
+		t.Fatal("expected an error for a file larger than the limit")
+	}
+}
+
+func TestBundleEntryLimit(t *testing.T) {
+	// entries with the same name must not get past the limit on the number of files
+	var buf bytes.Buffer
+	gw := gzip.NewWriter(&buf)
+	tw := tar.NewWriter(gw)
+	for i := 0; i <= maxFiles; i++ {
+		tw.WriteHeader(&tar.Header{Name: manifestFile, Mode: 0644})
+	}
+	tw.Close()
+	gw.Close()
+	_, _, err := Read(&buf)
+	if err == nil || !strings.Contains(err.Error(), "more than") {
+		t.Fatal("expected an error for too many entries, got", err)
+	}
+}
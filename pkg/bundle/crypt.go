@@ -0,0 +1,91 @@
+package bundle
+
+import (
+	"bytes"
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"errors"
+	"io"
+
+	"golang.org/x/crypto/scrypt"
+)
+
+// magic is the prefix of encrypted bundles. It is followed by the salt of the key, the nonce
+// and the sealed bundle.
+var magic = []byte("KEPLOY-ENC1\n")
+
+const (
+	saltSize = 16
+	keySize  = 32
+)
+
+// ErrPassphrase is returned by Decrypt when a bundle is encrypted and the passphrase is
+// missing or wrong.
+var ErrPassphrase = errors.New("bundle is encrypted: missing or wrong passphrase")
+
+// Encrypt seals a bundle with AES-256-GCM under a key derived from the passphrase, so that
+// bundles holding recorded credentials or user data can be uploaded to shared artifact stores.
+func Encrypt(w io.Writer, b []byte, passphrase string) error {
+	salt := make([]byte, saltSize)
+	if _, err := rand.Read(salt); err != nil {
+		return err
+	}
+	aead, err := newAEAD(passphrase, salt)
+	if err != nil {
+		return err
+	}
+	nonce := make([]byte, aead.NonceSize())
+	if _, err = rand.Read(nonce); err != nil {
+		return err
+	}
+	out := append(append(append([]byte{}, magic...), salt...), nonce...)
+	out = aead.Seal(out, nonce, b, magic)
+	_, err = w.Write(out)
+	return err
+}
+
+// Encrypted reports whether b was written by Encrypt.
+func Encrypted(b []byte) bool {
+	return bytes.HasPrefix(b, magic)
+}
+
+// Decrypt opens a bundle sealed by Encrypt.
+func Decrypt(b []byte, passphrase string) ([]byte, error) {
+	if !Encrypted(b) {
+		return nil, errors.New("bundle is not encrypted")
+	}
+	b = b[len(magic):]
+	if len(b) < saltSize {
+		return nil, errors.New("invalid encrypted bundle")
+	}
+	aead, err := newAEAD(passphrase, b[:saltSize])
+	if err != nil {
+		return nil, err
+	}
+	b = b[saltSize:]
+	if len(b) < aead.NonceSize() {
+		return nil, errors.New("invalid encrypted bundle")
+	}
+	res, err := aead.Open(nil, b[:aead.NonceSize()], b[aead.NonceSize():], magic)
+	if err != nil {
+		return nil, ErrPassphrase
+	}
+	return res, nil
+}
+
+func newAEAD(passphrase string, salt []byte) (cipher.AEAD, error) {
+	if passphrase == "" {
+		return nil, ErrPassphrase
+	}
+	// recommended parameters for interactive logins as of 2017
+	key, err := scrypt.Key([]byte(passphrase), salt, 1<<15, 8, 1, keySize)
+	if err != nil {
+		return nil, err
+	}
+	block, err := aes.NewCipher(key)
+	if err != nil {
+		return nil, err
+	}
+	return cipher.NewGCM(block)
+}
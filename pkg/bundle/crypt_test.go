@@ -0,0 +1,36 @@
+package bundle
+
+import (
+	"bytes"
+	"testing"
+)
+
+func TestEncrypt(t *testing.T) {
+	plain := []byte("bundle")
+	var buf bytes.Buffer
+	if err := Encrypt(&buf, plain, "secret"); err != nil {
+		t.Fatal(err)
+	}
+	if !Encrypted(buf.Bytes()) || Encrypted(plain) {
+		t.Fatal("expected only the sealed bundle to be reported as encrypted")
+	}
+	if bytes.Contains(buf.Bytes(), plain) {
+		t.Fatal("expected the bundle to be encrypted, got", buf.String())
+	}
+	for _, tt := range []struct {
+		passphrase string
+		err        error
+	}{
+		{passphrase: "secret"},
+		{passphrase: "wrong", err: ErrPassphrase},
+		{passphrase: "", err: ErrPassphrase},
+	} {
+		res, err := Decrypt(buf.Bytes(), tt.passphrase)
+		if err != tt.err {
+			t.Fatal("THIS IS EXP", tt.err, " \n THIS IS ACT", err)
+		}
+		if err == nil && !bytes.Equal(res, plain) {
+			t.Fatal("THIS IS EXP", string(plain), " \n THIS IS ACT", string(res))
+		}
+	}
+}
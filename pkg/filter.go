@@ -0,0 +1,123 @@
+package pkg
+
+import (
+	"fmt"
+	"net/url"
+	"regexp"
+	"strconv"
+	"strings"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+type statusRange struct {
+	from, to int
+}
+
+// RecordFilter is the compiled form of models.Filter.
+type RecordFilter struct {
+	config  models.Filter
+	include []*regexp.Regexp
+	exclude []*regexp.Regexp
+	methods []models.Method
+	status  []statusRange
+}
+
+// NewRecordFilter compiles the given filter. It returns an error for invalid path patterns
+// or status codes.
+func NewRecordFilter(f models.Filter) (*RecordFilter, error) {
+	rf := &RecordFilter{config: f}
+	for _, p := range f.IncludePaths {
+		re, err := regexp.Compile(p)
+		if err != nil {
+			return nil, fmt.Errorf("invalid include path %q: %v", p, err)
+		}
+		rf.include = append(rf.include, re)
+	}
+	for _, p := range f.ExcludePaths {
+		re, err := regexp.Compile(p)
+		if err != nil {
+			return nil, fmt.Errorf("invalid exclude path %q: %v", p, err)
+		}
+		rf.exclude = append(rf.exclude, re)
+	}
+	rf.methods = f.Methods
+	for _, s := range f.ExcludeStatus {
+		r, err := parseStatusRange(strings.TrimSpace(s))
+		if err != nil {
+			return nil, err
+		}
+		rf.status = append(rf.status, r)
+	}
+	return rf, nil
+}
+
+func parseStatusRange(s string) (statusRange, error) {
+	lower := strings.ToLower(s)
+	if len(lower) == 3 && strings.HasSuffix(lower, "xx") {
+		c, err := strconv.Atoi(lower[:1])
+		if err != nil {
+			return statusRange{}, fmt.Errorf("invalid status %q", s)
+		}
+		return statusRange{from: c * 100, to: c*100 + 99}, nil
+	}
+	parts := strings.SplitN(s, "-", 2)
+	from, err := strconv.Atoi(strings.TrimSpace(parts[0]))
+	if err != nil {
+		return statusRange{}, fmt.Errorf("invalid status %q", s)
+	}
+	to := from
+	if len(parts) == 2 {
+		to, err = strconv.Atoi(strings.TrimSpace(parts[1]))
+		if err != nil || to < from {
+			return statusRange{}, fmt.Errorf("invalid status range %q", s)
+		}
+	}
+	return statusRange{from: from, to: to}, nil
+}
+
+// Config returns the filter which was compiled.
+func (rf *RecordFilter) Config() models.Filter {
+	return rf.config
+}
+
+// Allow returns true if the testcase should be recorded.
+func (rf *RecordFilter) Allow(tc models.TestCase) bool {
+	path := tc.URI
+	if u, err := url.Parse(tc.HttpReq.URL); err == nil && u.Path != "" {
+		path = u.Path
+	}
+	if len(rf.include) > 0 && !matchAny(rf.include, path) {
+		return false
+	}
+	if matchAny(rf.exclude, path) {
+		return false
+	}
+	if len(rf.methods) > 0 {
+		found := false
+		for _, m := range rf.methods {
+			if strings.EqualFold(string(m), string(tc.HttpReq.Method)) {
+				found = true
+				break
+			}
+		}
+		if !found {
+			return false
+		}
+	}
+	for _, r := range rf.status {
+		if tc.HttpResp.StatusCode >= r.from && tc.HttpResp.StatusCode <= r.to {
+			return false
+		}
+	}
+	return true
+}
+
+func matchAny(res []*regexp.Regexp, s string) bool {
+	for _, re := range res {
+		if re.MatchString(s) {
+			return true
+		}
+	}
+	return false
+}
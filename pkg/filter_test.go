@@ -0,0 +1,52 @@
+package pkg
+
+import (
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+func TestRecordFilter(t *testing.T) {
+	f, err := NewRecordFilter(models.Filter{
+		IncludePaths:  []string{"^/api/"},
+		ExcludePaths:  []string{"^/api/healthz$", `\.(js|css|png)$`},
+		Methods:       []models.Method{"get", models.MethodPost},
+		ExcludeStatus: []string{"404", "5xx"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, tt := range []struct {
+		url    string
+		method models.Method
+		status int
+		result bool
+	}{
+		{url: "http://localhost:8080/api/users?limit=1", method: models.MethodGet, status: 200, result: true},
+		{url: "http://localhost:8080/api/users", method: models.MethodPost, status: 201, result: true},
+		{url: "http://localhost:8080/metrics", method: models.MethodGet, status: 200, result: false},
+		{url: "http://localhost:8080/api/healthz", method: models.MethodGet, status: 200, result: false},
+		{url: "http://localhost:8080/api/static/app.js", method: models.MethodGet, status: 200, result: false},
+		{url: "http://localhost:8080/api/users", method: models.MethodDelete, status: 200, result: false},
+		{url: "http://localhost:8080/api/users/1", method: models.MethodGet, status: 404, result: false},
+		{url: "http://localhost:8080/api/users/1", method: models.MethodGet, status: 503, result: false},
+	} {
+		tc := models.TestCase{
+			HttpReq:  models.HttpReq{URL: tt.url, Method: tt.method},
+			HttpResp: models.HttpResp{StatusCode: tt.status},
+		}
+		if res := f.Allow(tc); res != tt.result {
+			t.Fatal(tt.url, tt.method, tt.status, "THIS IS EXP", tt.result, " \n THIS IS ACT", res)
+		}
+	}
+
+	for _, bad := range []models.Filter{
+		{IncludePaths: []string{"("}},
+		{ExcludeStatus: []string{"abc"}},
+		{ExcludeStatus: []string{"599-500"}},
+	} {
+		if _, err := NewRecordFilter(bad); err == nil {
+			t.Fatal("expected an error for", bad)
+		}
+	}
+}
@@ -0,0 +1,433 @@
+// Package jsonpath implements the subset of JSONPath used for noise fields, transformation rules
+// and selective normalization.
+package jsonpath
+
+import (
+	"encoding/json"
+	"fmt"
+	"reflect"
+	"strconv"
+	"strings"
+)
+
+type kind int
+
+const (
+	kindKey kind = iota
+	kindIndex
+	kindWildcard
+	kindRecursive
+	kindFilter
+)
+
+// token is a single step of a parsed JSONPath expression.
+type token struct {
+	kind   kind
+	key    string
+	index  int
+	filter *predicate
+}
+
+// predicate is a filter expression like `[?(@.status == 'done')]`. When op is empty the
+// predicate only checks that the field exists.
+type predicate struct {
+	field []string
+	op    string
+	value interface{}
+}
+
+// Path is a parsed JSONPath expression.
+type Path []token
+
+// IsPath returns true if s is a JSONPath expression rather than a dot-delimited key.
+func IsPath(s string) bool {
+	return strings.HasPrefix(s, "$")
+}
+
+// Parse parses the supported subset of JSONPath: member access (`.key`, `['key']`),
+// array indices (`[0]`, `[-1]`), wildcards (`.*`, `[*]`), recursive descent (`..key`) and
+// simple filters (`[?(@.key)]`, `[?(@.key == 'value')]`, `[?(@.key != 1)]`).
+func Parse(expr string) (Path, error) {
+	if !IsPath(expr) {
+		return nil, fmt.Errorf("invalid jsonpath %q: must start with '$'", expr)
+	}
+	var tokens Path
+	i := 1
+	for i < len(expr) {
+		switch expr[i] {
+		case '.':
+			i++
+			if i < len(expr) && expr[i] == '.' {
+				tokens = append(tokens, token{kind: kindRecursive})
+				i++
+				if i < len(expr) && expr[i] == '[' {
+					continue
+				}
+			}
+			j := i
+			for j < len(expr) && expr[j] != '.' && expr[j] != '[' {
+				j++
+			}
+			name := expr[i:j]
+			if name == "" {
+				return nil, fmt.Errorf("invalid jsonpath %q: empty member name at position %d", expr, i)
+			}
+			if name == "*" {
+				tokens = append(tokens, token{kind: kindWildcard})
+			} else {
+				tokens = append(tokens, token{kind: kindKey, key: name})
+			}
+			i = j
+		case '[':
+			end := closingBracket(expr, i)
+			if end == -1 {
+				return nil, fmt.Errorf("invalid jsonpath %q: unclosed '[' at position %d", expr, i)
+			}
+			tok, err := parseBracket(strings.TrimSpace(expr[i+1 : end]))
+			if err != nil {
+				return nil, fmt.Errorf("invalid jsonpath %q: %v", expr, err)
+			}
+			tokens = append(tokens, tok)
+			i = end + 1
+		default:
+			return nil, fmt.Errorf("invalid jsonpath %q: unexpected character %q at position %d", expr, expr[i], i)
+		}
+	}
+	if len(tokens) == 0 {
+		return nil, fmt.Errorf("invalid jsonpath %q: must select at least one field", expr)
+	}
+	if tokens[len(tokens)-1].kind == kindRecursive {
+		return nil, fmt.Errorf("invalid jsonpath %q: recursive descent must be followed by a selector", expr)
+	}
+	return tokens, nil
+}
+
+// closingBracket returns the index of the ']' closing the '[' at start, skipping quoted strings.
+func closingBracket(expr string, start int) int {
+	var quote byte
+	for i := start + 1; i < len(expr); i++ {
+		c := expr[i]
+		switch {
+		case quote != 0:
+			if c == quote {
+				quote = 0
+			}
+		case c == '\'' || c == '"':
+			quote = c
+		case c == ']':
+			return i
+		}
+	}
+	return -1
+}
+
+func parseBracket(inner string) (token, error) {
+	switch {
+	case inner == "":
+		return token{}, fmt.Errorf("empty brackets")
+	case inner == "*":
+		return token{kind: kindWildcard}, nil
+	case strings.HasPrefix(inner, "?"):
+		p, err := parsePredicate(strings.TrimSpace(inner[1:]))
+		if err != nil {
+			return token{}, err
+		}
+		return token{kind: kindFilter, filter: p}, nil
+	case isQuoted(inner):
+		return token{kind: kindKey, key: inner[1 : len(inner)-1]}, nil
+	}
+	idx, err := strconv.Atoi(inner)
+	if err != nil {
+		return token{}, fmt.Errorf("invalid array index %q", inner)
+	}
+	return token{kind: kindIndex, index: idx}, nil
+}
+
+func parsePredicate(s string) (*predicate, error) {
+	if !strings.HasPrefix(s, "(") || !strings.HasSuffix(s, ")") {
+		return nil, fmt.Errorf("filter %q must be wrapped in parentheses", s)
+	}
+	s = strings.TrimSpace(s[1 : len(s)-1])
+	p := &predicate{}
+	left := s
+	for _, op := range []string{"==", "!="} {
+		if i := strings.Index(s, op); i != -1 {
+			p.op = op
+			left = strings.TrimSpace(s[:i])
+			right := strings.TrimSpace(s[i+len(op):])
+			v, err := parseLiteral(right)
+			if err != nil {
+				return nil, err
+			}
+			p.value = v
+			break
+		}
+	}
+	if left != "@" && !strings.HasPrefix(left, "@.") {
+		return nil, fmt.Errorf("filter field %q must start with '@.'", left)
+	}
+	if left != "@" {
+		p.field = strings.Split(left[2:], ".")
+		for _, f := range p.field {
+			if f == "" {
+				return nil, fmt.Errorf("filter field %q has an empty member name", left)
+			}
+		}
+	}
+	return p, nil
+}
+
+// parseLiteral converts a filter literal into the value encoding/json would decode it to.
+func parseLiteral(s string) (interface{}, error) {
+	if isQuoted(s) {
+		return s[1 : len(s)-1], nil
+	}
+	var v interface{}
+	if err := json.Unmarshal([]byte(s), &v); err != nil {
+		return nil, fmt.Errorf("invalid filter literal %q", s)
+	}
+	return v, nil
+}
+
+func isQuoted(s string) bool {
+	return len(s) >= 2 && (s[0] == '\'' || s[0] == '"') && s[len(s)-1] == s[0]
+}
+
+func (p *predicate) match(element interface{}) bool {
+	v := element
+	for _, f := range p.field {
+		m, ok := v.(map[string]interface{})
+		if !ok {
+			return false
+		}
+		v, ok = m[f]
+		if !ok {
+			return false
+		}
+	}
+	switch p.op {
+	case "==":
+		return reflect.DeepEqual(v, p.value)
+	case "!=":
+		return !reflect.DeepEqual(v, p.value)
+	default:
+		return true
+	}
+}
+
+// Remove removes every value selected by the path from the given element JSON.
+func Remove(element interface{}, path Path) interface{} {
+	return walk(element, path, func(interface{}) (interface{}, bool) {
+		return nil, false
+	})
+}
+
+// Update replaces every value selected by the path with the result of fn.
+func Update(element interface{}, path Path, fn func(interface{}) interface{}) interface{} {
+	return walk(element, path, func(v interface{}) (interface{}, bool) {
+		return fn(v), true
+	})
+}
+
+// walk calls visit for every value selected by the path in the given element
+// JSON. visit returns the new value and whether it should be kept; values which are not kept are
+// deleted from their parent object or array. It is a recursive function.
+func walk(element interface{}, path Path, visit func(interface{}) (interface{}, bool)) interface{} {
+	if len(path) == 0 {
+		return element
+	}
+	tok, rest := path[0], path[1:]
+	last := len(rest) == 0
+	switch tok.kind {
+	case kindKey:
+		m, ok := element.(map[string]interface{})
+		if !ok {
+			return element
+		}
+		v, ok := m[tok.key]
+		if !ok {
+			return element
+		}
+		if !last {
+			m[tok.key] = walk(v, rest, visit)
+		} else if nv, keep := visit(v); keep {
+			m[tok.key] = nv
+		} else {
+			delete(m, tok.key)
+		}
+		return m
+	case kindIndex:
+		a, ok := element.([]interface{})
+		if !ok {
+			return element
+		}
+		i := resolveIndex(tok.index, len(a))
+		if i == -1 {
+			return element
+		}
+		if !last {
+			a[i] = walk(a[i], rest, visit)
+		} else if nv, keep := visit(a[i]); keep {
+			a[i] = nv
+		} else {
+			return append(a[:i:i], a[i+1:]...)
+		}
+		return a
+	case kindWildcard, kindFilter:
+		selected := func(v interface{}) bool {
+			return tok.kind == kindWildcard || tok.filter.match(v)
+		}
+		switch e := element.(type) {
+		case map[string]interface{}:
+			for k, v := range e {
+				if !selected(v) {
+					continue
+				}
+				if !last {
+					e[k] = walk(v, rest, visit)
+				} else if nv, keep := visit(v); keep {
+					e[k] = nv
+				} else {
+					delete(e, k)
+				}
+			}
+			return e
+		case []interface{}:
+			res := []interface{}{}
+			for _, v := range e {
+				if !selected(v) {
+					res = append(res, v)
+					continue
+				}
+				if !last {
+					res = append(res, walk(v, rest, visit))
+				} else if nv, keep := visit(v); keep {
+					res = append(res, nv)
+				}
+			}
+			return res
+		}
+		return element
+	case kindRecursive:
+		// descend first so that changes at this level don't hide matching children.
+		switch e := element.(type) {
+		case map[string]interface{}:
+			for k, v := range e {
+				e[k] = walk(v, path, visit)
+			}
+		case []interface{}:
+			for i, v := range e {
+				e[i] = walk(v, path, visit)
+			}
+		}
+		return walk(element, rest, visit)
+	}
+	return element
+}
+
+// Copy replaces the values selected by the path in dst with the values at the same locations in
+// src. Selected values which are missing in src are removed from dst. Recursive descent is not
+// supported since the locations in dst and src can't be paired.
+func Copy(dst, src interface{}, path Path) (interface{}, error) {
+	for _, t := range path {
+		if t.kind == kindRecursive {
+			return dst, fmt.Errorf("recursive descent is not supported")
+		}
+	}
+	return copyValues(dst, src, path), nil
+}
+
+func copyValues(dst, src interface{}, path Path) interface{} {
+	if len(path) == 0 {
+		return src
+	}
+	tok, rest := path[0], path[1:]
+	switch tok.kind {
+	case kindKey:
+		d, ok1 := dst.(map[string]interface{})
+		s, ok2 := src.(map[string]interface{})
+		if !ok1 || !ok2 {
+			return dst
+		}
+		copyKey(d, s, tok.key, rest)
+		return d
+	case kindIndex:
+		d, ok1 := dst.([]interface{})
+		s, ok2 := src.([]interface{})
+		if !ok1 || !ok2 {
+			return dst
+		}
+		i, j := resolveIndex(tok.index, len(d)), resolveIndex(tok.index, len(s))
+		if i == -1 || j == -1 {
+			return dst
+		}
+		d[i] = copyValues(d[i], s[j], rest)
+		return d
+	case kindWildcard, kindFilter:
+		selected := func(v interface{}) bool {
+			return tok.kind == kindWildcard || tok.filter.match(v)
+		}
+		switch d := dst.(type) {
+		case map[string]interface{}:
+			s, ok := src.(map[string]interface{})
+			if !ok {
+				return dst
+			}
+			for k, v := range s {
+				if selected(v) {
+					copyKey(d, s, k, rest)
+				}
+			}
+			if len(rest) == 0 {
+				for k, v := range d {
+					if _, ok := s[k]; !ok && selected(v) {
+						delete(d, k)
+					}
+				}
+			}
+			return d
+		case []interface{}:
+			s, ok := src.([]interface{})
+			if !ok {
+				return dst
+			}
+			if len(rest) == 0 && tok.kind == kindWildcard {
+				return s
+			}
+			for i := 0; i < len(d) && i < len(s); i++ {
+				if selected(s[i]) {
+					d[i] = copyValues(d[i], s[i], rest)
+				}
+			}
+			return d
+		}
+	}
+	return dst
+}
+
+func copyKey(d, s map[string]interface{}, key string, rest Path) {
+	sv, ok := s[key]
+	if !ok {
+		if len(rest) == 0 {
+			delete(d, key)
+		}
+		return
+	}
+	dv, ok := d[key]
+	if !ok && len(rest) > 0 {
+		return
+	}
+	d[key] = copyValues(dv, sv, rest)
+}
+
+// resolveIndex returns the position of a (possibly negative) array index or -1 if it is out
+// of range.
+func resolveIndex(i, n int) int {
+	if i < 0 {
+		i = n + i
+	}
+	if i < 0 || i >= n {
+		return -1
+	}
+	return i
+}
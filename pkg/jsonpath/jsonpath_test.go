@@ -0,0 +1,69 @@
+package jsonpath
+
+import (
+	"encoding/json"
+	"reflect"
+	"testing"
+)
+
+func TestCopy(t *testing.T) {
+	for _, tt := range []struct {
+		dst    string
+		src    string
+		path   string
+		result string
+	}{
+		{
+			dst:    `{"name": "a", "updated": 1}`,
+			src:    `{"name": "b", "updated": 2}`,
+			path:   "$.updated",
+			result: `{"name": "a", "updated": 2}`,
+		},
+		{
+			dst:    `{"items": [{"id": 1, "ts": 1}, {"id": 2, "ts": 1}]}`,
+			src:    `{"items": [{"id": 3, "ts": 5}, {"id": 4, "ts": 6}]}`,
+			path:   "$.items[*].ts",
+			result: `{"items": [{"id": 1, "ts": 5}, {"id": 2, "ts": 6}]}`,
+		},
+		{
+			dst:    `{"items": [{"id": 1, "ts": 1}, {"id": 2, "ts": 1}]}`,
+			src:    `{"items": [{"id": 1, "ts": 5}, {"id": 2, "ts": 6}]}`,
+			path:   "$.items[?(@.id == 2)].ts",
+			result: `{"items": [{"id": 1, "ts": 1}, {"id": 2, "ts": 6}]}`,
+		},
+		// fields missing in the source are removed
+		{
+			dst:    `{"name": "a", "legacy": true}`,
+			src:    `{"name": "b"}`,
+			path:   "$.legacy",
+			result: `{"name": "a"}`,
+		},
+		{
+			dst:    `{"tags": ["a"], "name": "a"}`,
+			src:    `{"tags": ["a", "b"], "name": "b"}`,
+			path:   "$.tags[*]",
+			result: `{"tags": ["a", "b"], "name": "a"}`,
+		},
+	} {
+		var dst, src, exp interface{}
+		json.Unmarshal([]byte(tt.dst), &dst)
+		json.Unmarshal([]byte(tt.src), &src)
+		json.Unmarshal([]byte(tt.result), &exp)
+		p, err := Parse(tt.path)
+		if err != nil {
+			t.Fatal(err)
+		}
+		res, err := Copy(dst, src, p)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if !reflect.DeepEqual(res, exp) {
+			t.Fatal(tt.path, "THIS IS EXP", exp, " \n THIS IS ACT", res)
+		}
+	}
+
+	p, _ := Parse("$..ts")
+	if _, err := Copy(nil, nil, p); err == nil {
+		t.Fatal("expected an error for recursive descent")
+	}
+}
@@ -6,6 +6,7 @@ import (
 	"reflect"
 	"strings"
 
+	"go.keploy.io/server/pkg/jsonpath"
 	"go.uber.org/zap"
 )
 
@@ -30,8 +31,26 @@ func convertJson(s string, log *zap.Logger) (interface{}, error) {
 	}
 }
 
+// Match returns true if the expected and actual JSON strings are equal after removing the
+// noisy fields. Noise can either be dot-delimited keys or JSONPath expressions starting with "$".
 func Match(exp, act string, noise []string, log *zap.Logger) (bool, error) {
-	noiseMap := convertToMap(noise)
+	var (
+		keys  []string
+		paths []jsonpath.Path
+	)
+	for _, n := range noise {
+		if !IsJSONPath(n) {
+			keys = append(keys, n)
+			continue
+		}
+		p, err := jsonpath.Parse(n)
+		if err != nil {
+			log.Error("invalid jsonpath in noise", zap.String("noise", n), zap.Error(err))
+			return false, err
+		}
+		paths = append(paths, p)
+	}
+	noiseMap := convertToMap(keys)
 	expected, err := convertJson(exp, log)
 	if err != nil {
 		return false, err
@@ -48,9 +67,34 @@ func Match(exp, act string, noise []string, log *zap.Logger) (bool, error) {
 
 	tmp = mapClone(noiseMap)
 	actual = removeNoisy(actual, tmp)
+
+	for _, p := range paths {
+		expected = jsonpath.Remove(expected, p)
+		actual = jsonpath.Remove(actual, p)
+	}
 	return jsonMatch(expected, actual)
 }
 
+// IsJSONPath returns true if the noise field is a JSONPath expression rather than a
+// dot-delimited key.
+func IsJSONPath(s string) bool {
+	return jsonpath.IsPath(s)
+}
+
+// ValidateNoise returns an error for the first noise field which is an invalid JSONPath
+// expression. Dot-delimited noise fields are always valid.
+func ValidateNoise(noise []string) error {
+	for _, n := range noise {
+		if !IsJSONPath(n) {
+			continue
+		}
+		if _, err := jsonpath.Parse(n); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
 // removeNoisy removes the noisy key-value fields(storend in noise map) from given element JSON. It is a recursive function.
 func removeNoisy(element interface{}, noise map[string][]string) interface{} {
 	y := reflect.ValueOf(element)
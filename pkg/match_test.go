@@ -1196,3 +1196,92 @@ func TestJsonDiff(t *testing.T) {
 	}
 
 }
+
+func TestJsonPathNoise(t *testing.T) {
+	for _, tt := range []struct {
+		exp    string
+		actual string
+		noise  []string
+		result bool
+	}{
+		// wildcard over array elements
+		{
+			exp:    `{"items": [{"id": 1, "updated_at": "2022-01-01"}, {"id": 2, "updated_at": "2022-01-02"}]}`,
+			actual: `{"items": [{"id": 1, "updated_at": "2023-05-01"}, {"id": 2, "updated_at": "2023-05-02"}]}`,
+			noise:  []string{"$.items[*].updated_at"},
+			result: true,
+		},
+		// wildcard does not hide other differences
+		{
+			exp:    `{"items": [{"id": 1, "updated_at": "2022-01-01"}]}`,
+			actual: `{"items": [{"id": 3, "updated_at": "2023-05-01"}]}`,
+			noise:  []string{"$.items[*].updated_at"},
+			result: false,
+		},
+		// recursive descent
+		{
+			exp:    `{"ts": 1, "user": {"ts": 2, "name": "a"}, "list": [{"ts": 3}]}`,
+			actual: `{"ts": 4, "user": {"ts": 5, "name": "a"}, "list": [{"ts": 6}]}`,
+			noise:  []string{"$..ts"},
+			result: true,
+		},
+		// array index
+		{
+			exp:    `{"events": [{"at": 1}, {"at": 2}]}`,
+			actual: `{"events": [{"at": 9}, {"at": 2}]}`,
+			noise:  []string{"$.events[0].at"},
+			result: true,
+		},
+		// conditional path only applies to matching elements
+		{
+			exp:    `{"jobs": [{"state": "done", "finished": 10}, {"state": "queued", "finished": 0}]}`,
+			actual: `{"jobs": [{"state": "done", "finished": 20}, {"state": "queued", "finished": 0}]}`,
+			noise:  []string{"$.jobs[?(@.state == 'done')].finished"},
+			result: true,
+		},
+		{
+			exp:    `{"jobs": [{"state": "done", "finished": 10}, {"state": "queued", "finished": 0}]}`,
+			actual: `{"jobs": [{"state": "done", "finished": 10}, {"state": "queued", "finished": 5}]}`,
+			noise:  []string{"$.jobs[?(@.state == 'done')].finished"},
+			result: false,
+		},
+		// bracket notation mixed with dot-delimited noise
+		{
+			exp:    `{"meta": {"request-id": "abc"}, "time": 1}`,
+			actual: `{"meta": {"request-id": "xyz"}, "time": 2}`,
+			noise:  []string{"$.meta['request-id']", "time"},
+			result: true,
+		},
+	} {
+		logger, _ := zap.NewProduction()
+		defer logger.Sync()
+		res, err := Match(tt.exp, tt.actual, tt.noise, logger)
+		if err != nil {
+			t.Fatal(tt.noise, err)
+		}
+		if res != tt.result {
+			t.Fatal(tt.exp, tt.actual, tt.noise, "THIS IS EXP", tt.result, " \n THIS IS ACT", res)
+		}
+	}
+}
+
+func TestValidateNoise(t *testing.T) {
+	for _, tt := range []struct {
+		noise []string
+		valid bool
+	}{
+		{noise: []string{"body.id", "header.Date"}, valid: true},
+		{noise: []string{"$.items[*].id", "$..ts", "$['a b'][-1]"}, valid: true},
+		{noise: []string{"$.items[?(@.state != 1)]"}, valid: true},
+		{noise: []string{"$"}, valid: false},
+		{noise: []string{"$.items["}, valid: false},
+		{noise: []string{"$.items[abc]"}, valid: false},
+		{noise: []string{"$.a..b.."}, valid: false},
+		{noise: []string{"$.items[?(state == 1)]"}, valid: false},
+	} {
+		err := ValidateNoise(tt.noise)
+		if (err == nil) != tt.valid {
+			t.Fatal(tt.noise, "expected valid:", tt.valid, "got error:", err)
+		}
+	}
+}
@@ -0,0 +1,26 @@
+package models
+
+// HeaderAssertion is a rule for a single response header which replaces the comparison of that
+// header with the recorded one. Value is the expected value for EXACT and the regular
+// expression for REGEX, and it is not used by EXISTS and ABSENT.
+type HeaderAssertion struct {
+	Header string     `json:"header" bson:"header"`
+	Rule   AssertRule `json:"rule" bson:"rule"`
+	Value  string     `json:"value,omitempty" bson:"value,omitempty"`
+}
+
+type AssertRule string
+
+const (
+	// AssertExact passes if the header has exactly the given value.
+	AssertExact AssertRule = "EXACT"
+
+	// AssertRegex passes if every value of the header matches the given regular expression.
+	AssertRegex AssertRule = "REGEX"
+
+	// AssertExists passes if the header is set, whatever its value.
+	AssertExists AssertRule = "EXISTS"
+
+	// AssertAbsent passes if the header is not set.
+	AssertAbsent AssertRule = "ABSENT"
+)
@@ -0,0 +1,11 @@
+package models
+
+// Filter decides which captured testcases are stored in record mode. Paths are regular
+// expressions matched against the request path. ExcludeStatus entries are status codes
+// ("404"), ranges ("500-599") or classes ("5xx"). Empty lists don't filter anything.
+type Filter struct {
+	IncludePaths  []string `json:"include_paths"`
+	ExcludePaths  []string `json:"exclude_paths"`
+	Methods       []Method `json:"methods"`
+	ExcludeStatus []string `json:"exclude_status"`
+}
@@ -16,8 +16,27 @@ type TestCase struct {
 	AllKeys  map[string][]string `json:"all_keys" bson:"all_keys,omitempty"`
 	Anchors  map[string][]string `json:"anchors" bson:"anchors,omitempty"`
 	Noise    []string            `json:"noise" bson:"noise,omitempty"`
+
+	// Transforms are applied to the recorded and actual responses before comparing them.
+	Transforms []Transform `json:"transforms" bson:"transforms,omitempty"`
+
+	// HeaderAssertions replace the comparison of the asserted headers with the recorded ones.
+	HeaderAssertions []HeaderAssertion `json:"header_assertions" bson:"header_assertions,omitempty"`
+
+	// Name and Description are set by the application with the X-Keploy-Test-Name and
+	// X-Keploy-Test-Description response headers, or when the testcase is edited.
+	Name        string `json:"name" bson:"name,omitempty"`
+	Description string `json:"description" bson:"description,omitempty"`
 }
 
+const (
+	// TestNameHeader and TestDescriptionHeader can be set by the application on a response to
+	// name and describe the testcase recorded from it. They are neither saved with the response
+	// nor compared with the recorded one.
+	TestNameHeader        = "X-Keploy-Test-Name"
+	TestDescriptionHeader = "X-Keploy-Test-Description"
+)
+
 type TestCaseDB interface {
 	Upsert(context.Context, TestCase) error
 	UpdateTC(context.Context, TestCase) error
@@ -28,4 +47,5 @@ type TestCaseDB interface {
 	Exists(context.Context, TestCase) (bool, error)
 	DeleteByAnchor(ctx context.Context, cid, app, uri string, filterKeys map[string][]string) error
 	GetApps(ctx context.Context, cid string) ([]string, error)
+	Count(ctx context.Context, cid, app string) (int64, error)
 }
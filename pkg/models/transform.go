@@ -0,0 +1,24 @@
+package models
+
+// Transform is a rule applied to both the recorded and the actual response before they are
+// compared, so that systematic but benign differences don't have to be marked as noise.
+// Field is either "header.<name>" or a JSONPath expression on the response body. Key is the
+// field of the array elements to sort by and is only used by SORT.
+type Transform struct {
+	Type  TransformType `json:"type" bson:"type"`
+	Field string        `json:"field" bson:"field"`
+	Key   string        `json:"key,omitempty" bson:"key,omitempty"`
+}
+
+type TransformType string
+
+const (
+	// TransformSort sorts the selected arrays, by Key if given.
+	TransformSort TransformType = "SORT"
+
+	// TransformLowercase lowercases the selected header values or string body fields.
+	TransformLowercase TransformType = "LOWERCASE"
+
+	// TransformUnwrap replaces the body with the selected wrapped field.
+	TransformUnwrap TransformType = "UNWRAP"
+)
@@ -0,0 +1,149 @@
+package pkg
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"regexp"
+	"sort"
+	"strings"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+// idRegex matches UUIDs, ULIDs and long random hex tokens.
+var idRegex = regexp.MustCompile(`\b(?:[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}|[0-7][0-9A-HJKMNP-TV-Z]{25}|[0-9a-fA-F]{32,})\b`)
+
+// IDNormalizer replaces random identifiers with stable placeholders. The same identifier is
+// always replaced by the same placeholder, so values which are linked in the recorded data
+// must also be linked in the actual data for them to match.
+type IDNormalizer struct {
+	ids map[string]string
+}
+
+func NewIDNormalizer() *IDNormalizer {
+	return &IDNormalizer{ids: map[string]string{}}
+}
+
+// String replaces the identifiers in s. Placeholders are numbered in the order in which the
+// identifiers are first seen.
+func (n *IDNormalizer) String(s string) string {
+	return idRegex.ReplaceAllStringFunc(s, func(id string) string {
+		if !isRandomID(id) {
+			return id
+		}
+		p, ok := n.ids[id]
+		if !ok {
+			p = fmt.Sprintf("{{id_%d}}", len(n.ids)+1)
+			n.ids[id] = p
+		}
+		return p
+	})
+}
+
+// JSON replaces the identifiers in the keys and values of the given element JSON. Object keys
+// are visited in sorted order to keep the placeholder numbering deterministic.
+func (n *IDNormalizer) JSON(element interface{}) interface{} {
+	switch e := element.(type) {
+	case string:
+		return n.String(e)
+	case []interface{}:
+		res := make([]interface{}, len(e))
+		for i, v := range e {
+			res[i] = n.JSON(v)
+		}
+		return res
+	case map[string]interface{}:
+		keys := make([]string, 0, len(e))
+		for k := range e {
+			keys = append(keys, k)
+		}
+		sort.Strings(keys)
+		res := make(map[string]interface{}, len(e))
+		for _, k := range keys {
+			res[n.String(k)] = n.JSON(e[k])
+		}
+		return res
+	default:
+		return element
+	}
+}
+
+// Body replaces the identifiers in a JSON or plain text body.
+func (n *IDNormalizer) Body(body string) string {
+	if !json.Valid([]byte(body)) {
+		return n.String(body)
+	}
+	var result interface{}
+	if err := json.Unmarshal([]byte(body), &result); err != nil {
+		return n.String(body)
+	}
+	b, err := json.Marshal(n.JSON(result))
+	if err != nil {
+		return body
+	}
+	return string(b)
+}
+
+// Response returns a copy of the response with the identifiers in the headers and the body
+// replaced.
+func (n *IDNormalizer) Response(resp models.HttpResp) models.HttpResp {
+	var header http.Header
+	if resp.Header != nil {
+		keys := make([]string, 0, len(resp.Header))
+		for k := range resp.Header {
+			keys = append(keys, k)
+		}
+		sort.Strings(keys)
+		header = http.Header{}
+		for _, k := range keys {
+			for _, v := range resp.Header[k] {
+				header[k] = append(header[k], n.String(v))
+			}
+		}
+	}
+	return models.HttpResp{
+		StatusCode: resp.StatusCode,
+		Header:     header,
+		Body:       n.Body(resp.Body),
+	}
+}
+
+// isRandomID filters out hex matches which are plain words or numbers, eg: "deadbeef..." or a
+// long run of digits.
+func isRandomID(s string) bool {
+	if strings.Count(s, "-") == 4 {
+		return true
+	}
+	return strings.IndexAny(s, "0123456789") != -1 && strings.IndexAny(s, "abcdefABCDEFGHJKMNPQRSTVWXYZ") != -1
+}
+
+// NormalizeCookies returns a copy of the response with the values and expiry dates of the
+// cookies it sets replaced with placeholders. Session cookies differ on every run, so they
+// only have to be set with the same names and attributes to match.
+func NormalizeCookies(resp models.HttpResp) models.HttpResp {
+	values, ok := resp.Header["Set-Cookie"]
+	if !ok {
+		return resp
+	}
+	normalized := make([]string, len(values))
+	for i, v := range values {
+		parts := strings.Split(v, ";")
+		for j, p := range parts {
+			kv := strings.SplitN(strings.TrimSpace(p), "=", 2)
+			if len(kv) != 2 {
+				// flags like HttpOnly or Secure
+				continue
+			}
+			if j == 0 {
+				parts[j] = kv[0] + "={{cookie}}"
+			} else if strings.EqualFold(kv[0], "expires") {
+				parts[j] = " " + kv[0] + "={{expires}}"
+			}
+		}
+		normalized[i] = strings.Join(parts, ";")
+	}
+	resp.Header = resp.Header.Clone()
+	resp.Header["Set-Cookie"] = normalized
+	return resp
+}
@@ -0,0 +1,95 @@
+package pkg
+
+import (
+	"net/http"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+func TestIDNormalizer(t *testing.T) {
+	for _, tt := range []struct {
+		req    string
+		exp    string
+		actual string
+		result bool
+	}{
+		// freshly generated ids match
+		{
+			exp:    `{"id": "3f2b8c7e-1d4a-4b6e-9f0a-2c5d8e7f1a3b", "name": "a"}`,
+			actual: `{"id": "9a8b7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d", "name": "a"}`,
+			result: true,
+		},
+		// linked ids must stay linked
+		{
+			exp:    `{"id": "3f2b8c7e-1d4a-4b6e-9f0a-2c5d8e7f1a3b", "self": "/users/3f2b8c7e-1d4a-4b6e-9f0a-2c5d8e7f1a3b"}`,
+			actual: `{"id": "9a8b7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d", "self": "/users/01ARZ3NDEKTSV4RRFFQ69G5FAV"}`,
+			result: false,
+		},
+		// ids echoed from the request must be the same ones
+		{
+			req:    `{"parent": "01ARZ3NDEKTSV4RRFFQ69G5FAV"}`,
+			exp:    `{"parent": "01ARZ3NDEKTSV4RRFFQ69G5FAV", "token": "5f4dcc3b5aa765d61d8327deb882cf99"}`,
+			actual: `{"parent": "01BX5ZZKBKACTAV9WEVGEMMVRZ", "token": "e99a18c428cb38d5f260853678922e03"}`,
+			result: false,
+		},
+		{
+			req:    `{"parent": "01ARZ3NDEKTSV4RRFFQ69G5FAV"}`,
+			exp:    `{"parent": "01ARZ3NDEKTSV4RRFFQ69G5FAV", "token": "5f4dcc3b5aa765d61d8327deb882cf99"}`,
+			actual: `{"parent": "01ARZ3NDEKTSV4RRFFQ69G5FAV", "token": "e99a18c428cb38d5f260853678922e03"}`,
+			result: true,
+		},
+		// plain numbers are not ids
+		{
+			exp:    `{"count": "12345678901234567890123456789012"}`,
+			actual: `{"count": "99999999999999999999999999999999"}`,
+			result: false,
+		},
+	} {
+		logger, _ := zap.NewProduction()
+		defer logger.Sync()
+		en, an := NewIDNormalizer(), NewIDNormalizer()
+		en.Body(tt.req)
+		an.Body(tt.req)
+		res, err := Match(en.Body(tt.exp), an.Body(tt.actual), nil, logger)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if res != tt.result {
+			t.Fatal(tt.exp, tt.actual, "THIS IS EXP", tt.result, " \n THIS IS ACT", res)
+		}
+	}
+}
+
+func TestNormalizeCookies(t *testing.T) {
+	for _, tt := range []struct {
+		exp    []string
+		actual []string
+		result bool
+	}{
+		{
+			exp:    []string{"session=abc123; Path=/; Expires=Wed, 21 Oct 2022 07:28:00 GMT; HttpOnly"},
+			actual: []string{"session=xyz789; Path=/; Expires=Thu, 22 Oct 2023 10:00:00 GMT; HttpOnly"},
+			result: true,
+		},
+		// attributes still have to match
+		{
+			exp:    []string{"session=abc123; Path=/; HttpOnly"},
+			actual: []string{"session=xyz789; Path=/admin; HttpOnly"},
+			result: false,
+		},
+		{
+			exp:    []string{"session=abc123"},
+			actual: []string{"token=abc123"},
+			result: false,
+		},
+	} {
+		exp := NormalizeCookies(models.HttpResp{Header: http.Header{"Set-Cookie": tt.exp}})
+		act := NormalizeCookies(models.HttpResp{Header: http.Header{"Set-Cookie": tt.actual}})
+		if res := CompareHeaders(exp.Header, act.Header, &[]run.HeaderResult{}); res != tt.result {
+			t.Fatal(tt.exp, tt.actual, "THIS IS EXP", tt.result, " \n THIS IS ACT", res)
+		}
+	}
+}
@@ -0,0 +1,74 @@
+package github
+
+import (
+	"fmt"
+	"os"
+	"strings"
+
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+// maxSnippet is the maximum length of the expected and actual bodies shown in the annotations.
+const maxSnippet = 300
+
+// Enabled returns true when keploy is running inside a GitHub Actions workflow.
+func Enabled() bool {
+	return os.Getenv("GITHUB_ACTIONS") == "true"
+}
+
+// AnnotateFailure emits an error workflow command for the failed test, so that it shows up
+// inline on the pull request, and appends the failure to the job summary.
+func AnnotateFailure(t run.Test, log *zap.Logger) {
+	title := fmt.Sprintf("keploy test failed: %s %s", t.Req.Method, t.URI)
+	var details []string
+	if !t.Result.StatusCode.Normal {
+		details = append(details, fmt.Sprintf("status code: expected %d, got %d", t.Result.StatusCode.Expected, t.Result.StatusCode.Actual))
+	}
+	for _, h := range t.Result.HeadersResult {
+		if !h.Normal {
+			details = append(details, fmt.Sprintf("header %s: expected %v, got %v", h.Expected.Key, h.Expected.Value, h.Actual.Value))
+		}
+	}
+	if !t.Result.BodyResult.Normal {
+		details = append(details, "body: expected "+snippet(t.Result.BodyResult.Expected)+", got "+snippet(t.Result.BodyResult.Actual))
+	}
+	details = append(details, "test case: "+t.TestCaseID, "test run: "+t.RunID)
+
+	// the runner reads workflow commands from stderr as well, and stdout is left to the JSON
+	// logs so that their consumers only see JSON objects.
+	fmt.Fprintf(os.Stderr, "::error title=%s::%s\n", escapeProperty(title), escapeData(strings.Join(details, "\n")))
+
+	path := os.Getenv("GITHUB_STEP_SUMMARY")
+	if path == "" {
+		return
+	}
+	f, err := os.OpenFile(path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
+	if err != nil {
+		log.Error("failed to open the github job summary", zap.Error(err))
+		return
+	}
+	defer f.Close()
+	summary := fmt.Sprintf("### :x: %s\n\n```\n%s\n```\n\n", title, strings.Join(details, "\n"))
+	if _, err = f.WriteString(summary); err != nil {
+		log.Error("failed to write the github job summary", zap.Error(err))
+	}
+}
+
+func snippet(s string) string {
+	if len(s) > maxSnippet {
+		return s[:maxSnippet] + "..."
+	}
+	return s
+}
+
+// escapeData escapes the message of a workflow command.
+// Reference: https://github.com/actions/toolkit/blob/main/packages/core/src/command.ts
+func escapeData(s string) string {
+	return strings.NewReplacer("%", "%25", "\r", "%0D", "\n", "%0A").Replace(s)
+}
+
+// escapeProperty escapes the properties (eg: title) of a workflow command.
+func escapeProperty(s string) string {
+	return strings.NewReplacer("%", "%25", "\r", "%0D", "\n", "%0A", ":", "%3A", ",", "%2C").Replace(s)
+}
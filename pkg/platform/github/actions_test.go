@@ -0,0 +1,67 @@
+package github
+
+import (
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+func TestEscape(t *testing.T) {
+	for _, tt := range []struct {
+		input    string
+		data     string
+		property string
+	}{
+		{input: "GET /users", data: "GET /users", property: "GET /users"},
+		{input: "100%", data: "100%25", property: "100%25"},
+		{input: "expected 1\r\ngot 2", data: "expected 1%0D%0Agot 2", property: "expected 1%0D%0Agot 2"},
+		{input: "status: 500, body", data: "status: 500, body", property: "status%3A 500%2C body"},
+		// the % is escaped first, so the escapes aren't escaped again
+		{input: "%0A\n", data: "%250A%0A", property: "%250A%0A"},
+	} {
+		if res := escapeData(tt.input); res != tt.data {
+			t.Fatal(tt.input, "THIS IS EXP", tt.data, " \n THIS IS ACT", res)
+		}
+		if res := escapeProperty(tt.input); res != tt.property {
+			t.Fatal(tt.input, "THIS IS EXP", tt.property, " \n THIS IS ACT", res)
+		}
+	}
+}
+
+func TestAnnotateFailureSummary(t *testing.T) {
+	dir, err := ioutil.TempDir("", "keploy")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	path := filepath.Join(dir, "summary.md")
+	os.Setenv("GITHUB_STEP_SUMMARY", path)
+	defer os.Unsetenv("GITHUB_STEP_SUMMARY")
+
+	AnnotateFailure(run.Test{
+		RunID:      "run",
+		TestCaseID: "1",
+		URI:        "/users",
+		Req:        models.HttpReq{Method: models.MethodGet},
+		Result: run.Result{
+			StatusCode: run.IntResult{Expected: 200, Actual: 500},
+			BodyResult: run.BodyResult{Normal: true},
+		},
+	}, zap.NewNop())
+
+	b, err := ioutil.ReadFile(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, s := range []string{"keploy test failed: GET /users", "status code: expected 200, got 500", "test case: 1", "test run: run"} {
+		if !strings.Contains(string(b), s) {
+			t.Fatal("expected the job summary to contain", s, "got", string(b))
+		}
+	}
+}
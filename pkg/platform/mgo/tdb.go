@@ -58,6 +58,10 @@ func (t *testCaseDB) GetKeys(ctx context.Context, cid, app, uri string) ([]model
 	return t.getAll(ctx, filter, findOptions)
 }
 
+func (t *testCaseDB) Count(ctx context.Context, cid, app string) (int64, error) {
+	return t.c.CountDocuments(ctx, bson.M{"cid": cid, "app_id": app})
+}
+
 func (t *testCaseDB) Exists(ctx context.Context, tc models.TestCase) (bool, error) {
 	opts := options.Count().SetMaxTime(2 * time.Second)
 	filters := bson.M{
@@ -190,10 +194,22 @@ func (t *testCaseDB) DeleteByAnchor(ctx context.Context, cid, app, uri string, f
 	return nil
 }
 
-// UpdateTC only updates the http request and response of the given testcase.
+// UpdateTC only updates the http request, response, name, description and (if given) the noise
+// fields, transforms and header assertions of the testcase. An empty name or description
+// clears it.
 func (t *testCaseDB) UpdateTC(ctx context.Context, tc models.TestCase) error {
 	filter := bson.M{"_id": tc.ID}
-	update := bson.D{{"$set", bson.M{"http_req": tc.HttpReq, "http_resp": tc.HttpResp}}}
+	set := bson.M{"http_req": tc.HttpReq, "http_resp": tc.HttpResp, "name": tc.Name, "description": tc.Description}
+	if tc.Noise != nil {
+		set["noise"] = tc.Noise
+	}
+	if tc.Transforms != nil {
+		set["transforms"] = tc.Transforms
+	}
+	if tc.HeaderAssertions != nil {
+		set["header_assertions"] = tc.HeaderAssertions
+	}
+	update := bson.D{{"$set", set}}
 	_, err := t.c.UpdateOne(ctx, filter, update)
 	if err != nil {
 		return err
@@ -0,0 +1,99 @@
+package wiremock
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/url"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+// Mappings is the format of the files in the mappings directory of a WireMock server.
+// Reference: https://wiremock.org/docs/stubbing/
+type Mappings struct {
+	Mappings []Mapping `json:"mappings"`
+}
+
+type Mapping struct {
+	ID       string   `json:"id"`
+	Name     string   `json:"name,omitempty"`
+	Request  Request  `json:"request"`
+	Response Response `json:"response"`
+}
+
+type Request struct {
+	Method       string        `json:"method"`
+	URL          string        `json:"url"`
+	BodyPatterns []BodyPattern `json:"bodyPatterns,omitempty"`
+}
+
+type BodyPattern struct {
+	EqualToJSON string `json:"equalToJson,omitempty"`
+	EqualTo     string `json:"equalTo,omitempty"`
+}
+
+type Response struct {
+	Status  int         `json:"status"`
+	Headers http.Header `json:"headers,omitempty"`
+	Body    string      `json:"body,omitempty"`
+}
+
+// skipHeaders are not copied to the stubs. Content-Length and Date are recomputed by WireMock
+// for every response, and the recorded bodies are already decoded and unchunked.
+var skipHeaders = map[string]bool{
+	"Connection":        true,
+	"Content-Encoding":  true,
+	"Content-Length":    true,
+	"Date":              true,
+	"Keep-Alive":        true,
+	"Trailer":           true,
+	"Transfer-Encoding": true,
+}
+
+// FromTestCases converts recorded testcases into WireMock stubs, so that the consumers of an
+// application can run their tests against its recorded behaviour. Request headers are not
+// matched since they mostly differ between clients.
+func FromTestCases(tcs []models.TestCase) Mappings {
+	res := Mappings{Mappings: []Mapping{}}
+	for _, tc := range tcs {
+		m := Mapping{
+			ID:   tc.ID,
+			Name: string(tc.HttpReq.Method) + " " + tc.URI,
+			Request: Request{
+				Method: string(tc.HttpReq.Method),
+				URL:    requestURI(tc),
+			},
+			Response: Response{
+				Status: tc.HttpResp.StatusCode,
+				Body:   tc.HttpResp.Body,
+			},
+		}
+		if body := tc.HttpReq.Body; body != "" {
+			if json.Valid([]byte(body)) {
+				m.Request.BodyPatterns = []BodyPattern{{EqualToJSON: body}}
+			} else {
+				m.Request.BodyPatterns = []BodyPattern{{EqualTo: body}}
+			}
+		}
+		for k, v := range tc.HttpResp.Header {
+			if skipHeaders[http.CanonicalHeaderKey(k)] {
+				continue
+			}
+			if m.Response.Headers == nil {
+				m.Response.Headers = http.Header{}
+			}
+			m.Response.Headers[k] = v
+		}
+		res.Mappings = append(res.Mappings, m)
+	}
+	return res
+}
+
+// requestURI returns the path and the query of the recorded request.
+func requestURI(tc models.TestCase) string {
+	u, err := url.Parse(tc.HttpReq.URL)
+	if err != nil || u.Path == "" {
+		return tc.URI
+	}
+	return u.RequestURI()
+}
@@ -0,0 +1,69 @@
+package wiremock
+
+import (
+	"net/http"
+	"reflect"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+func TestFromTestCases(t *testing.T) {
+	tcs := []models.TestCase{
+		{
+			ID:  "1",
+			URI: "/users",
+			HttpReq: models.HttpReq{
+				Method: models.MethodPost,
+				URL:    "http://localhost:8080/users?notify=true",
+				Body:   `{"name": "alice"}`,
+			},
+			HttpResp: models.HttpResp{
+				StatusCode: http.StatusCreated,
+				Header: http.Header{
+					"Content-Type":      {"application/json"},
+					"Content-Encoding":  {"gzip"},
+					"Transfer-Encoding": {"chunked"},
+					"Content-Length":    {"12"},
+					"Date":              {"Mon, 14 Feb 2022 10:00:00 GMT"},
+				},
+				Body: `{"id": 1}`,
+			},
+		},
+		{
+			ID:       "2",
+			URI:      "/health",
+			HttpReq:  models.HttpReq{Method: models.MethodPut, Body: "ping"},
+			HttpResp: models.HttpResp{StatusCode: http.StatusOK},
+		},
+	}
+	exp := Mappings{Mappings: []Mapping{
+		{
+			ID:   "1",
+			Name: "POST /users",
+			Request: Request{
+				Method:       "POST",
+				URL:          "/users?notify=true",
+				BodyPatterns: []BodyPattern{{EqualToJSON: `{"name": "alice"}`}},
+			},
+			Response: Response{
+				Status:  http.StatusCreated,
+				Headers: http.Header{"Content-Type": {"application/json"}},
+				Body:    `{"id": 1}`,
+			},
+		},
+		{
+			ID:   "2",
+			Name: "PUT /health",
+			Request: Request{
+				Method:       "PUT",
+				URL:          "/health",
+				BodyPatterns: []BodyPattern{{EqualTo: "ping"}},
+			},
+			Response: Response{Status: http.StatusOK},
+		},
+	}}
+	if res := FromTestCases(tcs); !reflect.DeepEqual(res, exp) {
+		t.Fatal("THIS IS EXP", exp, " \n THIS IS ACT", res)
+	}
+}
@@ -18,23 +18,42 @@ import (
 	"github.com/google/uuid"
 	"go.keploy.io/server/pkg"
 	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/platform/github"
 	"go.keploy.io/server/pkg/platform/telemetry"
 	"go.keploy.io/server/pkg/service/run"
 	"go.uber.org/zap"
 )
 
-func New(tdb models.TestCaseDB, rdb run.DB, log *zap.Logger, EnableDeDup bool, adb telemetry.Service, client http.Client) *Regression {
+// Config is the configuration of the regression service.
+type Config struct {
+	EnableDeDup      bool
+	NormalizeIDs     bool
+	NormalizeCookies bool
+	// Filter drops testcases which shouldn't be recorded. It is optional.
+	Filter *pkg.RecordFilter
+	// MaxAge is the age after which testcases are stale. Zero disables it.
+	MaxAge      time.Duration
+	FailOnStale bool
+}
+
+func New(tdb models.TestCaseDB, rdb run.DB, log *zap.Logger, conf Config, adb telemetry.Service, client http.Client) *Regression {
 	return &Regression{
-		tdb:         tdb,
-		tele:        adb,
-		log:         log,
-		rdb:         rdb,
-		client:      client,
-		mu:          sync.Mutex{},
-		anchors:     map[string][]map[string][]string{},
-		noisyFields: map[string]map[string]bool{},
-		fieldCounts: map[string]map[string]map[string]int{},
-		EnableDeDup: EnableDeDup,
+		tdb:              tdb,
+		tele:             adb,
+		log:              log,
+		rdb:              rdb,
+		client:           client,
+		mu:               sync.Mutex{},
+		anchors:          map[string][]map[string][]string{},
+		noisyFields:      map[string]map[string]bool{},
+		fieldCounts:      map[string]map[string]map[string]int{},
+		EnableDeDup:      conf.EnableDeDup,
+		NormalizeIDs:     conf.NormalizeIDs,
+		NormalizeCookies: conf.NormalizeCookies,
+		filter:           conf.Filter,
+		maxAge:           conf.MaxAge,
+		failOnStale:      conf.FailOnStale,
+		ghActions:        github.Enabled(),
 	}
 }
 
@@ -63,6 +82,22 @@ type Regression struct {
 	// eg: lets say field is bloodGroup then the value would be {A+: 20, B+: 10,...}
 	fieldCounts map[string]map[string]map[string]int
 	EnableDeDup bool
+	// NormalizeIDs replaces UUIDs, ULIDs and random hex tokens with linked placeholders
+	// before comparing responses.
+	NormalizeIDs bool
+	// NormalizeCookies replaces the values of the cookies set by the responses before
+	// comparing them.
+	NormalizeCookies bool
+	// filter drops testcases which shouldn't be recorded, eg: health checks or static assets.
+	// It can be replaced while recording, so it is guarded by filterMu.
+	filter   *pkg.RecordFilter
+	filterMu sync.RWMutex
+	// maxAge is the age after which the recorded dependencies of a testcase are considered
+	// stale. Stale testcases fail if failOnStale is set.
+	maxAge      time.Duration
+	failOnStale bool
+	// ghActions is set when running inside GitHub Actions to annotate the failed tests.
+	ghActions bool
 }
 
 func (r *Regression) DeleteTC(ctx context.Context, cid, id string) error {
@@ -95,6 +130,31 @@ func (r *Regression) GetApps(ctx context.Context, cid string) ([]string, error)
 	return apps, err
 }
 
+func (r *Regression) Status(ctx context.Context, cid string) (Status, error) {
+	res := Status{
+		EnableDeDup:      r.EnableDeDup,
+		NormalizeIDs:     r.NormalizeIDs,
+		NormalizeCookies: r.NormalizeCookies,
+		FailOnStale:      r.failOnStale,
+		Filter:           r.GetFilter(ctx),
+		Apps:             []AppStatus{},
+	}
+	apps, err := r.tdb.GetApps(ctx, cid)
+	if err != nil {
+		r.log.Error("failed to get apps from the DB", zap.String("cid", cid), zap.Error(err))
+		return res, errors.New("internal failure")
+	}
+	for _, app := range apps {
+		count, err := r.tdb.Count(ctx, cid, app)
+		if err != nil {
+			r.log.Error("failed to count testcases in the DB", zap.String("cid", cid), zap.String("appID", app), zap.Error(err))
+			return res, errors.New("internal failure")
+		}
+		res.Apps = append(res.Apps, AppStatus{App: app, TestCases: count})
+	}
+	return res, nil
+}
+
 // sanitiseInput sanitises user input strings before logging them for safety, removing newlines
 // and escaping HTML tags. This is to prevent log injection, including forgery of log records.
 // Reference: https://www.owasp.org/index.php/Log_Injection
@@ -133,6 +193,12 @@ func (r *Regression) GetAll(ctx context.Context, cid, appID string, offset *int,
 }
 
 func (r *Regression) UpdateTC(ctx context.Context, t []models.TestCase) error {
+	for _, v := range t {
+		if err := pkg.ValidateTestCase(v); err != nil {
+			r.log.Error("invalid testcase", zap.String("id", v.ID), zap.Error(err))
+			return err
+		}
+	}
 	for _, v := range t {
 		err := r.tdb.UpdateTC(ctx, v)
 		if err != nil {
@@ -144,8 +210,107 @@ func (r *Regression) UpdateTC(ctx context.Context, t []models.TestCase) error {
 	return nil
 }
 
+// Export returns all the testcases of an app, including the keys used for deduplication.
+func (r *Regression) Export(ctx context.Context, cid, appID string) ([]models.TestCase, error) {
+	tcs, err := r.tdb.GetAll(ctx, cid, appID, true, 0, 0)
+	if err != nil {
+		r.log.Error("failed to get testcases from the DB", zap.String("cid", cid), zap.String("appID", sanitiseInput(appID)), zap.Error(err))
+		return nil, errors.New("internal failure")
+	}
+	return tcs, nil
+}
+
+// Import saves testcases which weren't recorded by the SDK, eg: exported or hand-written
+// ones, under the given app or under their own app if it is empty. Testcases imported under
+// another app get new ids. Unlike Put, the record filters and deduplication are not applied.
+func (r *Regression) Import(ctx context.Context, cid, appID string, t []models.TestCase) ([]string, error) {
+	for _, v := range t {
+		if err := pkg.ValidateTestCase(v); err != nil {
+			r.log.Error("invalid testcase", zap.String("id", v.ID), zap.Error(err))
+			return nil, err
+		}
+	}
+	var ids []string
+	for _, v := range t {
+		v.CID = cid
+		if appID != "" && appID != v.AppID {
+			// copy the testcases instead of moving them if the source app is on this server
+			v.ID = uuid.New().String()
+			v.AppID = appID
+		}
+		if err := r.tdb.Upsert(ctx, v); err != nil {
+			r.log.Error("failed to insert testcase into DB", zap.String("cid", cid), zap.String("appID", v.AppID), zap.Error(err))
+			return ids, errors.New("internal failure")
+		}
+		ids = append(ids, v.ID)
+
+		// reset cache, so that the imported testcases are used for deduplication
+		index := fmt.Sprintf("%s-%s-%s", v.CID, v.AppID, v.URI)
+		r.mu.Lock()
+		delete(r.anchors, index)
+		delete(r.noisyFields, index)
+		delete(r.fieldCounts, index)
+		r.mu.Unlock()
+	}
+	return ids, nil
+}
+
+func (r *Regression) GetFilter(_ context.Context) models.Filter {
+	r.filterMu.RLock()
+	defer r.filterMu.RUnlock()
+	if r.filter == nil {
+		return models.Filter{}
+	}
+	return r.filter.Config()
+}
+
+// SetFilter replaces the record filters. The changes apply to the testcases captured after it
+// returns.
+func (r *Regression) SetFilter(ctx context.Context, f models.Filter) error {
+	filter, err := pkg.NewRecordFilter(f)
+	if err != nil {
+		r.log.Error("invalid record filters", zap.Error(err))
+		return err
+	}
+	old := r.GetFilter(ctx)
+
+	r.filterMu.Lock()
+	r.filter = filter
+	r.filterMu.Unlock()
+
+	for _, c := range []struct {
+		name     string
+		old, new interface{}
+	}{
+		{"include paths", old.IncludePaths, f.IncludePaths},
+		{"exclude paths", old.ExcludePaths, f.ExcludePaths},
+		{"methods", old.Methods, f.Methods},
+		{"exclude status", old.ExcludeStatus, f.ExcludeStatus},
+	} {
+		if !reflect.DeepEqual(c.old, c.new) {
+			r.log.Info("record filter changed", zap.String("setting", c.name), zap.Any("from", c.old), zap.Any("to", c.new), zap.Time("applies from", time.Now().UTC()))
+		}
+	}
+	return nil
+}
+
 func (r *Regression) putTC(ctx context.Context, cid string, t models.TestCase) (string, error) {
 	t.CID = cid
+	if name := t.HttpResp.Header.Get(models.TestNameHeader); name != "" {
+		t.Name = name
+	}
+	if desc := t.HttpResp.Header.Get(models.TestDescriptionHeader); desc != "" {
+		t.Description = desc
+	}
+	t.HttpResp.Header = withoutTestHeaders(t.HttpResp.Header)
+
+	r.filterMu.RLock()
+	filter := r.filter
+	r.filterMu.RUnlock()
+	if filter != nil && !filter.Allow(t) {
+		r.log.Debug("testcase filtered out", zap.String("cid", cid), zap.String("appID", t.AppID), zap.String("uri", sanitiseInput(t.URI)))
+		return "", nil
+	}
 
 	var err error
 	if r.EnableDeDup {
@@ -165,6 +330,7 @@ func (r *Regression) putTC(ctx context.Context, cid string, t models.TestCase) (
 		r.log.Error("failed to insert testcase into DB", zap.String("cid", cid), zap.String("appID", t.AppID), zap.Error(err))
 		return "", errors.New("internal failure")
 	}
+	r.log.Info("testcase recorded", zap.String("id", t.ID), zap.String("appID", t.AppID), zap.String("uri", sanitiseInput(t.URI)))
 
 	return t.ID, nil
 }
@@ -216,6 +382,10 @@ func (r *Regression) test(ctx context.Context, cid, id, app string, resp models.
 	var noise []string
 
 	for _, n := range tc.Noise {
+		if pkg.IsJSONPath(n) {
+			noise = append(noise, n)
+			continue
+		}
 		a := strings.Split(n, ".")
 		if len(a) > 1 && a[0] == "body" {
 			x := strings.Join(a[1:], ".")
@@ -223,20 +393,47 @@ func (r *Regression) test(ctx context.Context, cid, id, app string, resp models.
 		}
 	}
 
+	// the recorded response never has them
+	resp.Header = withoutTestHeaders(resp.Header)
+
+	// apply the transformation rules to both the responses before comparing them.
+	exp, act := tc.HttpResp, resp
+	if len(tc.Transforms) > 0 {
+		exp, err = pkg.Transform(tc.HttpResp, tc.Transforms)
+		if err != nil {
+			return false, res, &tc, err
+		}
+		act, err = pkg.Transform(resp, tc.Transforms)
+		if err != nil {
+			return false, res, &tc, err
+		}
+	}
+
+	if r.NormalizeIDs {
+		exp, act = normalizeIDs(tc.HttpReq, exp, act)
+	}
+	if r.NormalizeCookies {
+		exp, act = pkg.NormalizeCookies(exp), pkg.NormalizeCookies(act)
+	}
+
 	if bodyType == run.BodyTypeJSON {
-		pass, err = pkg.Match(tc.HttpResp.Body, resp.Body, noise, r.log)
+		pass, err = pkg.Match(exp.Body, act.Body, noise, r.log)
 		if err != nil {
 			return false, res, &tc, err
 		}
 	} else {
-		if !pkg.Contains(tc.Noise, "body") && tc.HttpResp.Body != resp.Body {
+		if !pkg.Contains(tc.Noise, "body") && exp.Body != act.Body {
 			pass = false
 		}
 	}
 
 	res.BodyResult.Normal = pass
 
-	if !pkg.CompareHeaders(tc.HttpResp.Header, resp.Header, hRes) {
+	expHeader, actHeader, ok := pkg.AssertHeaders(tc.HeaderAssertions, exp.Header, act.Header, hRes)
+	if !ok {
+		pass = false
+	}
+	if !pkg.CompareHeaders(expHeader, actHeader, hRes) {
 		pass = false
 	}
 	res.HeadersResult = *hRes
@@ -247,26 +444,70 @@ func (r *Regression) test(ctx context.Context, cid, id, app string, resp models.
 		pass = false
 	}
 
+	if r.isStale(tc) {
+		r.log.Warn("testcase mocks are stale, re-record the testcase", zap.String("id", tc.ID), zap.String("appID", tc.AppID), zap.String("uri", tc.URI), zap.Time("captured", time.Unix(tc.Captured, 0)))
+		if r.failOnStale {
+			pass = false
+		}
+	}
+
 	return pass, res, &tc, nil
 }
 
+// withoutTestHeaders returns the headers without the ones naming and describing the testcase.
+func withoutTestHeaders(h http.Header) http.Header {
+	_, name := h[models.TestNameHeader]
+	_, desc := h[models.TestDescriptionHeader]
+	if !name && !desc {
+		return h
+	}
+	h = h.Clone()
+	h.Del(models.TestNameHeader)
+	h.Del(models.TestDescriptionHeader)
+	return h
+}
+
+// isStale returns true if the testcase was captured more than maxAge ago, in which case its
+// recorded dependencies may have drifted from the real ones. Only the age is checked since the
+// dependencies recorded by the SDK don't carry the version of the dependency.
+func (r *Regression) isStale(tc models.TestCase) bool {
+	if r.maxAge <= 0 || tc.Captured == 0 {
+		return false
+	}
+	return time.Now().UTC().Sub(time.Unix(tc.Captured, 0)) > r.maxAge
+}
+
+// normalizeIDs replaces the random identifiers in both the responses with placeholders. Both
+// normalizers are seeded with the recorded request so that identifiers echoed from the request
+// get the same placeholders on both sides.
+func normalizeIDs(req models.HttpReq, exp, act models.HttpResp) (models.HttpResp, models.HttpResp) {
+	en, an := pkg.NewIDNormalizer(), pkg.NewIDNormalizer()
+	for _, n := range []*pkg.IDNormalizer{en, an} {
+		n.String(req.URL)
+		n.Body(req.Body)
+	}
+	return en.Response(exp), an.Response(act)
+}
+
 func (r *Regression) Test(ctx context.Context, cid, app, runID, id string, resp models.HttpResp) (bool, error) {
-	var t *run.Test
 	started := time.Now().UTC()
 	ok, res, tc, err := r.test(ctx, cid, id, app, resp)
-	if tc != nil {
-		t = &run.Test{
-			ID:         uuid.New().String(),
-			Started:    started.Unix(),
-			RunID:      runID,
-			TestCaseID: id,
-			URI:        tc.URI,
-			Req:        tc.HttpReq,
-			Dep:        tc.Deps,
-			Resp:       resp,
-			Result:     *res,
-			Noise:      tc.Noise,
-		}
+	if tc == nil {
+		// there is no testcase to save the result for
+		return false, err
+	}
+	t := &run.Test{
+		ID:         uuid.New().String(),
+		Started:    started.Unix(),
+		RunID:      runID,
+		TestCaseID: id,
+		URI:        tc.URI,
+		Req:        tc.HttpReq,
+		Dep:        tc.Deps,
+		Resp:       resp,
+		Result:     *res,
+		Noise:      tc.Noise,
+		Stale:      r.isStale(*tc),
 	}
 	t.Completed = time.Now().UTC().Unix()
 	defer func() {
@@ -282,9 +523,14 @@ func (r *Regression) Test(ctx context.Context, cid, app, runID, id string, resp
 	}
 	if ok {
 		t.Status = run.TestStatusPassed
+		r.log.Info("test passed", zap.String("test run", runID), zap.String("testcase", id), zap.String("appID", app))
 		return ok, nil
 	}
 	t.Status = run.TestStatusFailed
+	r.log.Info("test failed", zap.String("test run", runID), zap.String("testcase", id), zap.String("appID", app))
+	if r.ghActions {
+		github.AnnotateFailure(*t, r.log)
+	}
 	return false, nil
 }
 
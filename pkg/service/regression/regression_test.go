@@ -0,0 +1,47 @@
+package regression
+
+import (
+	"context"
+	"net/http"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+	"go.uber.org/zap"
+)
+
+func TestNamedTestCase(t *testing.T) {
+	tdb := &mockTestCaseDB{}
+	r := &Regression{tdb: tdb, rdb: &mockRunDB{}, log: zap.NewNop()}
+	resp := models.HttpResp{
+		StatusCode: http.StatusCreated,
+		Header: http.Header{
+			"Content-Type":               {"application/json"},
+			models.TestNameHeader:        {"create user"},
+			models.TestDescriptionHeader: {"creates a user with a unique email"},
+		},
+		Body: `{"id": 1}`,
+	}
+	_, err := r.Put(context.Background(), "cid", []models.TestCase{{
+		ID:       "1",
+		AppID:    "app",
+		URI:      "/users",
+		HttpReq:  models.HttpReq{Method: models.MethodPost, URL: "/users"},
+		HttpResp: resp,
+	}})
+	if err != nil {
+		t.Fatal(err)
+	}
+	tc := tdb.tcs[0]
+	if tc.Name != "create user" || tc.Description != "creates a user with a unique email" {
+		t.Fatal("THIS IS EXP", "create user", " \n THIS IS ACT", tc.Name, tc.Description)
+	}
+	if _, ok := tc.HttpResp.Header[models.TestNameHeader]; ok {
+		t.Fatal("expected the name header not to be recorded, got", tc.HttpResp.Header)
+	}
+
+	// the application still sets the headers when it is tested
+	ok, err := r.Test(context.Background(), "cid", "app", "run", "1", resp)
+	if err != nil || !ok {
+		t.Fatal("expected the named testcase to pass, got", ok, err)
+	}
+}
@@ -0,0 +1,335 @@
+package regression
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/ioutil"
+	"net/http"
+	"net/url"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+	"go.keploy.io/server/pkg/jsonpath"
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+// replayTimeout is the timeout of every request replayed against a remote deployment.
+const replayTimeout = 30 * time.Second
+
+// maxReplayConcurrency is the maximum number of testcases replayed at the same time, so that a
+// replay can't exhaust the connections of the server or overload the deployment under test.
+const maxReplayConcurrency = 64
+
+// Replay sends the recorded requests of all the testcases of an app to a deployment of the app
+// at baseURL, eg: a staging environment, and tests the responses in a new test run. The
+// testcases are replayed in the order in which they were captured.
+// Dependencies are not mocked, so it is meant as an API smoke test. Up to concurrency chains of
+// testcases (at most maxReplayConcurrency) are replayed at the same time, to find bugs which
+// only show up under concurrent requests. The testcases of a chain are replayed one after the
+// other, see replayChains.
+// With auth, the recorded bearer tokens are replaced with a freshly minted one.
+func (r *Regression) Replay(ctx context.Context, cid, user, app, baseURL string, concurrency int, auth *TokenAuth) (run.TestRun, error) {
+	base, err := url.Parse(baseURL)
+	if err != nil || base.Scheme == "" || base.Host == "" {
+		return run.TestRun{}, errors.New("invalid base url " + baseURL)
+	}
+	tcs, err := r.tdb.GetAll(ctx, cid, app, false, 0, 0)
+	if err != nil {
+		r.log.Error("failed to get testcases from the DB", zap.String("cid", cid), zap.String("appID", sanitiseInput(app)), zap.Error(err))
+		return run.TestRun{}, errors.New("internal failure")
+	}
+	// the testcases are returned newest first, and flows like create-then-get have to be replayed
+	// in the order in which they were recorded
+	sort.SliceStable(tcs, func(i, j int) bool {
+		return capturedAt(tcs[i]) < capturedAt(tcs[j])
+	})
+
+	client := &http.Client{
+		Timeout: replayTimeout,
+		// the recorded responses are the ones returned by the app itself
+		CheckRedirect: func(*http.Request, []*http.Request) error {
+			return http.ErrUseLastResponse
+		},
+	}
+	var token string
+	if auth != nil {
+		token, err = fetchToken(ctx, client, *auth)
+		if err != nil {
+			r.log.Error("failed to fetch the auth token", zap.String("url", sanitiseInput(auth.URL)), zap.Error(err))
+			return run.TestRun{}, err
+		}
+	}
+
+	now := time.Now().Unix()
+	tr := run.TestRun{
+		ID:      uuid.New().String(),
+		Created: now,
+		Updated: now,
+		Status:  run.TestRunStatusRunning,
+		CID:     cid,
+		App:     app,
+		User:    user,
+		Total:   len(tcs),
+	}
+	if err = r.rdb.Upsert(ctx, tr); err != nil {
+		r.log.Error("failed to create the test run", zap.String("cid", cid), zap.String("appID", sanitiseInput(app)), zap.Error(err))
+		return tr, errors.New("internal failure")
+	}
+
+	if concurrency < 1 {
+		concurrency = 1
+	}
+	if concurrency > maxReplayConcurrency {
+		concurrency = maxReplayConcurrency
+	}
+	var (
+		wg      sync.WaitGroup
+		mu      sync.Mutex
+		sem     = make(chan struct{}, concurrency)
+		samples []latencySample
+	)
+	tr.Status = run.TestRunStatusPassed
+	replay := func(tc models.TestCase) {
+		ok := false
+		defer func() {
+			// a panic in a goroutine would crash the server
+			if v := recover(); v != nil {
+				r.log.Error("panic while replaying the testcase", zap.String("id", tc.ID), zap.Any("panic", v))
+			}
+			mu.Lock()
+			defer mu.Unlock()
+			if !ok {
+				tr.Status = run.TestRunStatusFailed
+				tr.Failure++
+				return
+			}
+			tr.Success++
+		}()
+		started := time.Now()
+		resp, err := replayRequest(ctx, client, base, tc, token)
+		if err != nil {
+			// the empty response fails the test, so that the failure shows up in the test run
+			r.log.Error("failed to replay the testcase", zap.String("id", tc.ID), zap.String("uri", sanitiseInput(tc.URI)), zap.Error(err))
+		} else {
+			d := time.Since(started)
+			mu.Lock()
+			samples = append(samples, latencySample{method: tc.HttpReq.Method, uri: tc.URI, d: d})
+			mu.Unlock()
+		}
+		ok, err = r.Test(ctx, cid, app, tr.ID, tc.ID, resp)
+		if err != nil {
+			ok = false
+		}
+	}
+	for _, chain := range replayChains(tcs) {
+		sem <- struct{}{}
+		wg.Add(1)
+		go func(chain []models.TestCase) {
+			defer func() {
+				<-sem
+				wg.Done()
+			}()
+			for _, tc := range chain {
+				replay(tc)
+			}
+		}(chain)
+	}
+	wg.Wait()
+
+	tr.Updated = time.Now().Unix()
+	tr.Latency = endpointLatency(samples)
+	if ctx.Err() != nil {
+		// the replay was cancelled, eg: the server is shutting down. The test run is still
+		// updated so that it isn't left running.
+		tr.Status = run.TestRunStatusFailed
+		ctx = context.Background()
+	}
+	if err = r.rdb.Upsert(ctx, run.TestRun{ID: tr.ID, Updated: tr.Updated, Status: tr.Status, Latency: tr.Latency}); err != nil {
+		r.log.Error("failed to update the test run", zap.String("cid", cid), zap.String("id", tr.ID), zap.Error(err))
+		return tr, errors.New("internal failure")
+	}
+	r.log.Info("test run completed", zap.String("test run", tr.ID), zap.String("appID", app), zap.String("status", string(tr.Status)),
+		zap.Int("success", tr.Success), zap.Int("failure", tr.Failure), zap.Int("total", tr.Total))
+	return tr, nil
+}
+
+// replayChains groups the testcases into chains, keeping their order. Testcases recorded with
+// the same session, ie: the same Cookie and Authorization headers, form a chain since they
+// usually depend on each other, eg: a user is created and then read. Testcases without a
+// session are independent of each other.
+func replayChains(tcs []models.TestCase) [][]models.TestCase {
+	var chains [][]models.TestCase
+	sessions := map[string]int{}
+	for _, tc := range tcs {
+		session := tc.HttpReq.Header.Get("Cookie") + "\n" + tc.HttpReq.Header.Get("Authorization")
+		if session == "\n" {
+			chains = append(chains, []models.TestCase{tc})
+			continue
+		}
+		if i, ok := sessions[session]; ok {
+			chains[i] = append(chains[i], tc)
+			continue
+		}
+		sessions[session] = len(chains)
+		chains = append(chains, []models.TestCase{tc})
+	}
+	return chains
+}
+
+// capturedAt returns when the testcase was captured, or created for testcases without a
+// capture time.
+func capturedAt(tc models.TestCase) int64 {
+	if tc.Captured != 0 {
+		return tc.Captured
+	}
+	return tc.Created
+}
+
+type latencySample struct {
+	method models.Method
+	uri    string
+	d      time.Duration
+}
+
+// endpointLatency returns the p50, p95 and max response times of every endpoint, in the order
+// in which the endpoints were first replayed.
+func endpointLatency(samples []latencySample) []run.EndpointLatency {
+	var order []string
+	durations := map[string][]time.Duration{}
+	endpoints := map[string]run.EndpointLatency{}
+	for _, s := range samples {
+		key := string(s.method) + " " + s.uri
+		if _, ok := endpoints[key]; !ok {
+			endpoints[key] = run.EndpointLatency{Method: s.method, URI: s.uri}
+			order = append(order, key)
+		}
+		durations[key] = append(durations[key], s.d)
+	}
+	var res []run.EndpointLatency
+	for _, key := range order {
+		d := durations[key]
+		sort.Slice(d, func(i, j int) bool { return d[i] < d[j] })
+		l := endpoints[key]
+		l.Count = len(d)
+		l.P50 = percentile(d, 50).Milliseconds()
+		l.P95 = percentile(d, 95).Milliseconds()
+		l.Max = d[len(d)-1].Milliseconds()
+		res = append(res, l)
+	}
+	return res
+}
+
+// percentile returns the nearest-rank percentile of the sorted durations.
+func percentile(sorted []time.Duration, p int) time.Duration {
+	i := (p*len(sorted)+99)/100 - 1
+	if i < 0 {
+		i = 0
+	}
+	return sorted[i]
+}
+
+// skipHeaders are the recorded request headers which are not replayed. They are either set by
+// the client for the new request or only apply to the recorded connection. Accept-Encoding is
+// left to the transport, which only decompresses the responses when it sets the header itself.
+var skipHeaders = map[string]bool{
+	"Accept-Encoding":   true,
+	"Connection":        true,
+	"Content-Length":    true,
+	"Host":              true,
+	"Keep-Alive":        true,
+	"Proxy-Connection":  true,
+	"Te":                true,
+	"Trailer":           true,
+	"Transfer-Encoding": true,
+	"Upgrade":           true,
+}
+
+// fetchToken posts the credentials to the token endpoint and returns the token from the response.
+func fetchToken(ctx context.Context, client *http.Client, auth TokenAuth) (string, error) {
+	field := auth.Field
+	if field == "" {
+		field = "$.access_token"
+	}
+	path, err := jsonpath.Parse(field)
+	if err != nil {
+		return "", err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, auth.URL, strings.NewReader(auth.Body))
+	if err != nil {
+		return "", err
+	}
+	req.Header.Set("Content-Type", "application/json")
+	resp, err := client.Do(req)
+	if err != nil {
+		return "", err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode/100 != 2 {
+		return "", fmt.Errorf("token endpoint returned status %d", resp.StatusCode)
+	}
+	var body interface{}
+	if err = json.NewDecoder(resp.Body).Decode(&body); err != nil {
+		return "", err
+	}
+	var token string
+	jsonpath.Update(body, path, func(v interface{}) interface{} {
+		if s, ok := v.(string); ok && token == "" {
+			token = s
+		}
+		return v
+	})
+	if token == "" {
+		return "", fmt.Errorf("no token at %s in the token response", field)
+	}
+	return token, nil
+}
+
+func replayRequest(ctx context.Context, client *http.Client, base *url.URL, tc models.TestCase, token string) (models.HttpResp, error) {
+	u, err := url.Parse(tc.HttpReq.URL)
+	if err != nil {
+		return models.HttpResp{}, err
+	}
+	// keep the escaping of the recorded path, eg: "%2F" in a path segment
+	target := *base
+	target.RawPath = strings.TrimSuffix(base.EscapedPath(), "/") + u.EscapedPath()
+	if target.Path, err = url.PathUnescape(target.RawPath); err != nil {
+		return models.HttpResp{}, err
+	}
+	target.RawQuery = u.RawQuery
+
+	req, err := http.NewRequestWithContext(ctx, string(tc.HttpReq.Method), target.String(), strings.NewReader(tc.HttpReq.Body))
+	if err != nil {
+		return models.HttpResp{}, err
+	}
+	for k, v := range tc.HttpReq.Header {
+		if skipHeaders[http.CanonicalHeaderKey(k)] {
+			continue
+		}
+		req.Header[k] = v
+	}
+	if auth := req.Header.Get("Authorization"); token != "" && strings.HasPrefix(strings.ToLower(auth), "bearer ") {
+		req.Header.Set("Authorization", "Bearer "+token)
+	}
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return models.HttpResp{}, err
+	}
+	defer resp.Body.Close()
+	body, err := ioutil.ReadAll(resp.Body)
+	if err != nil {
+		return models.HttpResp{}, err
+	}
+	return models.HttpResp{
+		StatusCode: resp.StatusCode,
+		Header:     resp.Header,
+		Body:       string(body),
+	}, nil
+}
@@ -0,0 +1,350 @@
+package regression
+
+import (
+	"compress/gzip"
+	"context"
+	"errors"
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"reflect"
+	"strconv"
+	"strings"
+	"sync"
+	"testing"
+	"time"
+
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+func TestReplayRequest(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.EscapedPath() != "/api/files/a%2Fb" || r.URL.RawQuery != "v=1" {
+			w.WriteHeader(http.StatusNotFound)
+			return
+		}
+		if r.Header.Get("X-Request") != "1" {
+			w.WriteHeader(http.StatusBadRequest)
+			return
+		}
+		if !strings.Contains(r.Header.Get("Accept-Encoding"), "gzip") {
+			w.Write([]byte("plain"))
+			return
+		}
+		w.Header().Set("Content-Encoding", "gzip")
+		gw := gzip.NewWriter(w)
+		gw.Write([]byte("plain"))
+		gw.Close()
+	}))
+	defer srv.Close()
+
+	base, _ := url.Parse(srv.URL + "/api/")
+	resp, err := replayRequest(context.Background(), srv.Client(), base, models.TestCase{
+		HttpReq: models.HttpReq{
+			Method: models.MethodGet,
+			URL:    "/files/a%2Fb?v=1",
+			Header: http.Header{
+				"X-Request":       {"1"},
+				"Accept-Encoding": {"gzip, deflate"},
+				"Connection":      {"keep-alive"},
+			},
+		},
+	}, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if resp.StatusCode != http.StatusOK || resp.Body != "plain" {
+		t.Fatal("THIS IS EXP", 200, "plain", " \n THIS IS ACT", resp.StatusCode, resp.Body)
+	}
+}
+
+func TestReplayTokenAuth(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		switch r.URL.Path {
+		case "/token":
+			b, _ := ioutil.ReadAll(r.Body)
+			if string(b) != `{"client_id": "ci"}` {
+				w.WriteHeader(http.StatusUnauthorized)
+				return
+			}
+			w.Write([]byte(`{"data": {"token": "fresh"}}`))
+		default:
+			w.Write([]byte(r.Header.Get("Authorization")))
+		}
+	}))
+	defer srv.Close()
+
+	token, err := fetchToken(context.Background(), srv.Client(), TokenAuth{URL: srv.URL + "/token", Body: `{"client_id": "ci"}`, Field: "$.data.token"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if token != "fresh" {
+		t.Fatal("THIS IS EXP", "fresh", " \n THIS IS ACT", token)
+	}
+	if _, err = fetchToken(context.Background(), srv.Client(), TokenAuth{URL: srv.URL + "/token"}); err == nil {
+		t.Fatal("expected an error for rejected credentials")
+	}
+
+	base, _ := url.Parse(srv.URL)
+	for _, tt := range []struct {
+		auth   string
+		result string
+	}{
+		{auth: "Bearer expired", result: "Bearer fresh"},
+		// other schemes are not templated
+		{auth: "Basic dXNlcjpwYXNz", result: "Basic dXNlcjpwYXNz"},
+		{auth: "", result: ""},
+	} {
+		tc := models.TestCase{HttpReq: models.HttpReq{Method: models.MethodGet, URL: "/me", Header: http.Header{}}}
+		if tt.auth != "" {
+			tc.HttpReq.Header.Set("Authorization", tt.auth)
+		}
+		resp, err := replayRequest(context.Background(), srv.Client(), base, tc, token)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if resp.Body != tt.result {
+			t.Fatal(tt.auth, "THIS IS EXP", tt.result, " \n THIS IS ACT", resp.Body)
+		}
+	}
+}
+
+func TestEndpointLatency(t *testing.T) {
+	var samples []latencySample
+	for i := 1; i <= 20; i++ {
+		samples = append(samples, latencySample{method: models.MethodGet, uri: "/users", d: time.Duration(i) * time.Millisecond})
+	}
+	samples = append(samples, latencySample{method: models.MethodPost, uri: "/users", d: 7 * time.Millisecond})
+
+	res := endpointLatency(samples)
+	exp := []run.EndpointLatency{
+		{Method: models.MethodGet, URI: "/users", Count: 20, P50: 10, P95: 19, Max: 20},
+		{Method: models.MethodPost, URI: "/users", Count: 1, P50: 7, P95: 7, Max: 7},
+	}
+	if !reflect.DeepEqual(res, exp) {
+		t.Fatal("THIS IS EXP", exp, " \n THIS IS ACT", res)
+	}
+}
+
+// mockTestCaseDB keeps the testcases in memory; only the methods used by the tests are
+// implemented.
+type mockTestCaseDB struct {
+	models.TestCaseDB
+	tcs []models.TestCase
+	// deleted testcases are still returned by GetAll, as if they were deleted during a replay
+	deleted map[string]bool
+}
+
+func (m *mockTestCaseDB) Upsert(ctx context.Context, tc models.TestCase) error {
+	m.tcs = append(m.tcs, tc)
+	return nil
+}
+
+func (m *mockTestCaseDB) GetAll(ctx context.Context, cid, app string, anchors bool, offset int, limit int) ([]models.TestCase, error) {
+	return m.tcs, nil
+}
+
+func (m *mockTestCaseDB) Get(ctx context.Context, cid, id string) (models.TestCase, error) {
+	for _, tc := range m.tcs {
+		if tc.ID == id && !m.deleted[id] {
+			return tc, nil
+		}
+	}
+	return models.TestCase{}, errors.New("testcase not found")
+}
+
+// mockRunDB records the test runs and the tests saved by Replay.
+type mockRunDB struct {
+	run.DB
+	mu    sync.Mutex
+	runs  []run.TestRun
+	tests []run.Test
+}
+
+func (m *mockRunDB) Upsert(ctx context.Context, tr run.TestRun) error {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.runs = append(m.runs, tr)
+	return nil
+}
+
+func (m *mockRunDB) PutTest(ctx context.Context, t run.Test) error {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.tests = append(m.tests, t)
+	return nil
+}
+
+func (m *mockRunDB) Increment(ctx context.Context, success, failure bool, id string) error {
+	return nil
+}
+
+func TestReplay(t *testing.T) {
+	var (
+		mu                sync.Mutex
+		inFlight, maxSeen int
+	)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		mu.Lock()
+		inFlight++
+		if inFlight > maxSeen {
+			maxSeen = inFlight
+		}
+		mu.Unlock()
+		time.Sleep(20 * time.Millisecond)
+		mu.Lock()
+		inFlight--
+		mu.Unlock()
+
+		w.Header().Set("Content-Type", "text/plain; charset=utf-8")
+		if strings.HasPrefix(r.URL.Path, "/changed") {
+			w.Write([]byte("changed"))
+			return
+		}
+		w.Write([]byte("ok"))
+	}))
+	defer srv.Close()
+
+	var tcs []models.TestCase
+	for i := 0; i < 12; i++ {
+		path := "/ok/" + strconv.Itoa(i)
+		if i%3 == 0 {
+			path = "/changed/" + strconv.Itoa(i)
+		}
+		tcs = append(tcs, models.TestCase{
+			ID:      strconv.Itoa(i),
+			URI:     path,
+			HttpReq: models.HttpReq{Method: models.MethodGet, URL: "http://localhost:8080" + path},
+			HttpResp: models.HttpResp{
+				StatusCode: http.StatusOK,
+				Header:     http.Header{"Content-Type": {"text/plain; charset=utf-8"}},
+				Body:       "ok",
+			},
+		})
+	}
+
+	for _, tt := range []struct {
+		concurrency int
+		maxInFlight int
+	}{
+		{concurrency: 0, maxInFlight: 1},
+		{concurrency: 4, maxInFlight: 4},
+		{concurrency: 1000, maxInFlight: len(tcs)},
+	} {
+		rdb := &mockRunDB{}
+		r := &Regression{tdb: &mockTestCaseDB{tcs: tcs}, rdb: rdb, log: zap.NewNop()}
+		maxSeen = 0
+		tr, err := r.Replay(context.Background(), "cid", "user", "app", srv.URL, tt.concurrency, nil)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if tr.Status != run.TestRunStatusFailed || tr.Total != 12 || tr.Success != 8 || tr.Failure != 4 {
+			t.Fatal("THIS IS EXP", "failed run with 8 successes and 4 failures", " \n THIS IS ACT", tr)
+		}
+		if len(rdb.tests) != len(tcs) {
+			t.Fatal("THIS IS EXP", len(tcs), " \n THIS IS ACT", len(rdb.tests))
+		}
+		if last := rdb.runs[len(rdb.runs)-1]; last.Status != run.TestRunStatusFailed || len(last.Latency) != len(tcs) {
+			t.Fatal("THIS IS EXP", "failed run with the latency of every endpoint", " \n THIS IS ACT", last)
+		}
+		if maxSeen > tt.maxInFlight || (tt.maxInFlight > 1 && maxSeen < 2) {
+			t.Fatal("THIS IS EXP", tt.maxInFlight, "concurrent requests at most", " \n THIS IS ACT", maxSeen)
+		}
+	}
+}
+
+func TestReplayDeletedTestCase(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Write([]byte("ok"))
+	}))
+	defer srv.Close()
+
+	tc := models.TestCase{
+		URI:     "/ok",
+		HttpReq: models.HttpReq{Method: models.MethodGet, URL: "/ok"},
+		HttpResp: models.HttpResp{
+			StatusCode: http.StatusOK,
+			Header:     http.Header{"Content-Type": {"text/plain; charset=utf-8"}},
+			Body:       "ok",
+		},
+	}
+	tdb := &mockTestCaseDB{deleted: map[string]bool{"2": true}}
+	for _, id := range []string{"1", "2"} {
+		tc.ID = id
+		tdb.tcs = append(tdb.tcs, tc)
+	}
+	r := &Regression{tdb: tdb, rdb: &mockRunDB{}, log: zap.NewNop()}
+	tr, err := r.Replay(context.Background(), "cid", "user", "app", srv.URL, 2, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if tr.Status != run.TestRunStatusFailed || tr.Success != 1 || tr.Failure != 1 {
+		t.Fatal("THIS IS EXP", "failed run with 1 success and 1 failure", " \n THIS IS ACT", tr)
+	}
+}
+
+func TestReplayOrder(t *testing.T) {
+	var requests []string
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		requests = append(requests, r.Method+" "+r.URL.Path)
+	}))
+	defer srv.Close()
+
+	// newest first, as returned by the DB
+	tdb := &mockTestCaseDB{tcs: []models.TestCase{
+		{ID: "3", Captured: 30, URI: "/users/1", HttpReq: models.HttpReq{Method: models.MethodDelete, URL: "/users/1"}},
+		{ID: "2", Captured: 20, URI: "/users/1", HttpReq: models.HttpReq{Method: models.MethodGet, URL: "/users/1"}},
+		{ID: "1", Captured: 10, URI: "/users", HttpReq: models.HttpReq{Method: models.MethodPost, URL: "/users"}},
+	}}
+	r := &Regression{tdb: tdb, rdb: &mockRunDB{}, log: zap.NewNop()}
+	if _, err := r.Replay(context.Background(), "cid", "user", "app", srv.URL, 1, nil); err != nil {
+		t.Fatal(err)
+	}
+	if exp := []string{"POST /users", "GET /users/1", "DELETE /users/1"}; !reflect.DeepEqual(requests, exp) {
+		t.Fatal("THIS IS EXP", exp, " \n THIS IS ACT", requests)
+	}
+}
+
+func TestReplayChains(t *testing.T) {
+	alice := http.Header{"Cookie": {"session=alice"}}
+	bob := http.Header{"Authorization": {"Bearer bob"}}
+	tcs := []models.TestCase{
+		{ID: "1", HttpReq: models.HttpReq{Header: alice}},
+		{ID: "2", HttpReq: models.HttpReq{Header: bob}},
+		{ID: "3"},
+		{ID: "4", HttpReq: models.HttpReq{Header: alice}},
+		{ID: "5"},
+		{ID: "6", HttpReq: models.HttpReq{Header: bob}},
+	}
+	var res [][]string
+	for _, chain := range replayChains(tcs) {
+		var ids []string
+		for _, tc := range chain {
+			ids = append(ids, tc.ID)
+		}
+		res = append(res, ids)
+	}
+	if exp := [][]string{{"1", "4"}, {"2", "6"}, {"3"}, {"5"}}; !reflect.DeepEqual(res, exp) {
+		t.Fatal("THIS IS EXP", exp, " \n THIS IS ACT", res)
+	}
+}
+
+func TestReplayCancelled(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	defer srv.Close()
+
+	rdb := &mockRunDB{}
+	tdb := &mockTestCaseDB{tcs: []models.TestCase{{ID: "1", URI: "/", HttpReq: models.HttpReq{Method: models.MethodGet, URL: "/"}}}}
+	r := &Regression{tdb: tdb, rdb: rdb, log: zap.NewNop()}
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+	if _, err := r.Replay(ctx, "cid", "user", "app", srv.URL, 1, nil); err != nil {
+		t.Fatal(err)
+	}
+	if last := rdb.runs[len(rdb.runs)-1]; last.Status != run.TestRunStatusFailed {
+		t.Fatal("THIS IS EXP", run.TestRunStatusFailed, " \n THIS IS ACT", last.Status)
+	}
+}
@@ -5,6 +5,7 @@ import (
 	"net/http"
 
 	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
 )
 
 type Service interface {
@@ -13,7 +14,41 @@ type Service interface {
 	Put(ctx context.Context, cid string, t []models.TestCase) ([]string, error)
 	DeNoise(ctx context.Context, cid, id, app, body string, h http.Header) error
 	Test(ctx context.Context, cid, app, runID, id string, resp models.HttpResp) (bool, error)
+	Replay(ctx context.Context, cid, user, app, baseURL string, concurrency int, auth *TokenAuth) (run.TestRun, error)
 	GetApps(ctx context.Context, cid string) ([]string, error)
 	UpdateTC(ctx context.Context, t []models.TestCase) error
 	DeleteTC(ctx context.Context, cid, id string) error
+	// GetFilter and SetFilter read and replace the record filters without restarting the server.
+	GetFilter(ctx context.Context) models.Filter
+	SetFilter(ctx context.Context, f models.Filter) error
+	// Export and Import move all the testcases of an app between keploy servers as they are.
+	// Import also saves hand-written testcases.
+	Export(ctx context.Context, cid, appID string) ([]models.TestCase, error)
+	Import(ctx context.Context, cid, appID string, t []models.TestCase) ([]string, error)
+	Status(ctx context.Context, cid string) (Status, error)
+}
+
+// TokenAuth is a token endpoint which is called before replaying testcases, so that recorded
+// short-lived tokens don't fail the tests once they expire. Body holds the credentials which
+// are posted to URL as JSON and Field is the JSONPath of the token in the response, by default
+// "$.access_token".
+type TokenAuth struct {
+	URL   string `json:"url"`
+	Body  string `json:"body"`
+	Field string `json:"field"`
+}
+
+// Status is the configuration of the server and the number of testcases recorded for every app.
+type Status struct {
+	EnableDeDup      bool          `json:"enable_dedup"`
+	NormalizeIDs     bool          `json:"normalize_ids"`
+	NormalizeCookies bool          `json:"normalize_cookies"`
+	FailOnStale      bool          `json:"fail_on_stale"`
+	Filter           models.Filter `json:"filter"`
+	Apps             []AppStatus   `json:"apps"`
+}
+
+type AppStatus struct {
+	App       string `json:"app"`
+	TestCases int64  `json:"test_cases"`
 }
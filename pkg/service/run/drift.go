@@ -0,0 +1,159 @@
+package run
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"sort"
+
+	"go.keploy.io/server/pkg/models"
+	"go.uber.org/zap"
+)
+
+// EndpointDrift summarises how the responses of an endpoint changed from the recorded ones
+// during a test run. Fields are JSONPath like, eg: "$.items[*].id".
+type EndpointDrift struct {
+	Method        models.Method  `json:"method"`
+	URI           string         `json:"uri"`
+	Tests         int            `json:"tests"`
+	AddedFields   []string       `json:"added_fields"`
+	RemovedFields []string       `json:"removed_fields"`
+	TypeChanges   []TypeChange   `json:"type_changes"`
+	StatusChanges []StatusChange `json:"status_changes"`
+}
+
+type TypeChange struct {
+	Field    string `json:"field"`
+	Expected string `json:"expected"`
+	Actual   string `json:"actual"`
+}
+
+type StatusChange struct {
+	Expected int `json:"expected"`
+	Actual   int `json:"actual"`
+}
+
+// Drift compares the recorded and actual responses of all the tests of the test run and
+// returns the endpoints whose response schema or status code changed.
+func (r *Run) Drift(ctx context.Context, cid, runID string) ([]EndpointDrift, error) {
+	tests, err := r.rdb.ReadTests(ctx, runID)
+	if err != nil {
+		msg := "failed getting tests from DB"
+		r.log.Error(msg, zap.String("cid", cid), zap.String("test run id", runID), zap.Error(err))
+		return nil, errors.New(msg)
+	}
+	return endpointDrift(tests), nil
+}
+
+// endpointDrift groups the tests by endpoint and collects the schema and status code changes
+// of each endpoint, in the order in which the endpoints were first tested.
+func endpointDrift(tests []Test) []EndpointDrift {
+	type drift struct {
+		EndpointDrift
+		added, removed map[string]bool
+		types          map[TypeChange]bool
+		status         map[StatusChange]bool
+	}
+	var order []string
+	endpoints := map[string]*drift{}
+	for _, t := range tests {
+		key := string(t.Req.Method) + " " + t.URI
+		d, ok := endpoints[key]
+		if !ok {
+			d = &drift{
+				EndpointDrift: EndpointDrift{Method: t.Req.Method, URI: t.URI},
+				added:         map[string]bool{},
+				removed:       map[string]bool{},
+				types:         map[TypeChange]bool{},
+				status:        map[StatusChange]bool{},
+			}
+			endpoints[key] = d
+			order = append(order, key)
+		}
+		d.Tests++
+
+		sc := t.Result.StatusCode
+		if sc.Expected != sc.Actual {
+			d.status[StatusChange{Expected: sc.Expected, Actual: sc.Actual}] = true
+		}
+		exp, act := schema(t.Result.BodyResult.Expected), schema(t.Result.BodyResult.Actual)
+		for f, et := range exp {
+			at, ok := act[f]
+			if !ok {
+				d.removed[f] = true
+				continue
+			}
+			if et != at {
+				d.types[TypeChange{Field: f, Expected: et, Actual: at}] = true
+			}
+		}
+		for f := range act {
+			if _, ok := exp[f]; !ok {
+				d.added[f] = true
+			}
+		}
+	}
+
+	var res []EndpointDrift
+	for _, key := range order {
+		d := endpoints[key]
+		if len(d.added)+len(d.removed)+len(d.types)+len(d.status) == 0 {
+			continue
+		}
+		d.AddedFields, d.RemovedFields = sortedKeys(d.added), sortedKeys(d.removed)
+		for tc := range d.types {
+			d.TypeChanges = append(d.TypeChanges, tc)
+		}
+		sort.Slice(d.TypeChanges, func(i, j int) bool { return d.TypeChanges[i].Field < d.TypeChanges[j].Field })
+		for sc := range d.status {
+			d.StatusChanges = append(d.StatusChanges, sc)
+		}
+		sort.Slice(d.StatusChanges, func(i, j int) bool { return d.StatusChanges[i].Actual < d.StatusChanges[j].Actual })
+		res = append(res, d.EndpointDrift)
+	}
+	return res
+}
+
+// schema returns the type of every field of a json body. Bodies which aren't valid json are
+// reported as text.
+func schema(body string) map[string]string {
+	var v interface{}
+	if err := json.Unmarshal([]byte(body), &v); err != nil {
+		return map[string]string{"$": "text"}
+	}
+	s := map[string]string{}
+	addSchema("$", v, s)
+	return s
+}
+
+func addSchema(path string, v interface{}, s map[string]string) {
+	switch e := v.(type) {
+	case map[string]interface{}:
+		s[path] = "object"
+		for k, c := range e {
+			addSchema(path+"."+k, c, s)
+		}
+	case []interface{}:
+		s[path] = "array"
+		for _, c := range e {
+			addSchema(path+"[*]", c, s)
+		}
+	case string:
+		s[path] = "string"
+	case float64:
+		s[path] = "number"
+	case bool:
+		s[path] = "boolean"
+	case nil:
+		s[path] = "null"
+	}
+}
+
+func sortedKeys(m map[string]bool) []string {
+	var res []string
+	for k := range m {
+		res = append(res, k)
+	}
+	sort.Strings(res)
+	return res
+}
@@ -0,0 +1,71 @@
+package run
+
+import (
+	"reflect"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+func TestEndpointDrift(t *testing.T) {
+	test := func(method models.Method, uri string, expStatus, actStatus int, exp, act string) Test {
+		return Test{
+			URI: uri,
+			Req: models.HttpReq{Method: method},
+			Result: Result{
+				StatusCode: IntResult{Expected: expStatus, Actual: actStatus},
+				BodyResult: BodyResult{Expected: exp, Actual: act},
+			},
+		}
+	}
+	for _, tt := range []struct {
+		name   string
+		tests  []Test
+		result []EndpointDrift
+	}{
+		{
+			name:   "unchanged endpoints are not reported",
+			tests:  []Test{test(models.MethodGet, "/users", 200, 200, `{"id": 1}`, `{"id": 2}`)},
+			result: nil,
+		},
+		{
+			name: "added, removed and type changed fields",
+			tests: []Test{
+				test(models.MethodGet, "/users", 200, 200,
+					`{"items": [{"id": 1, "name": "a"}], "total": 1}`,
+					`{"items": [{"id": "1", "email": "a@b.c"}], "total": 1}`),
+			},
+			result: []EndpointDrift{{
+				Method:        models.MethodGet,
+				URI:           "/users",
+				Tests:         1,
+				AddedFields:   []string{"$.items[*].email"},
+				RemovedFields: []string{"$.items[*].name"},
+				TypeChanges:   []TypeChange{{Field: "$.items[*].id", Expected: "number", Actual: "string"}},
+			}},
+		},
+		{
+			name: "changes are merged per endpoint",
+			tests: []Test{
+				test(models.MethodPost, "/users", 201, 500, `{"id": 1}`, `{"error": "x"}`),
+				test(models.MethodGet, "/users", 200, 200, `{"id": 1}`, `{"id": 1}`),
+				test(models.MethodPost, "/users", 201, 409, `{"id": 1}`, `{"error": "x"}`),
+				test(models.MethodPost, "/users", 201, 500, `plain`, `{"error": "x"}`),
+			},
+			result: []EndpointDrift{{
+				Method:        models.MethodPost,
+				URI:           "/users",
+				Tests:         3,
+				AddedFields:   []string{"$.error"},
+				RemovedFields: []string{"$.id"},
+				TypeChanges:   []TypeChange{{Field: "$", Expected: "text", Actual: "object"}},
+				StatusChanges: []StatusChange{{Expected: 201, Actual: 409}, {Expected: 201, Actual: 500}},
+			}},
+		},
+	} {
+		res := endpointDrift(tt.tests)
+		if !reflect.DeepEqual(res, tt.result) {
+			t.Fatal(tt.name, "THIS IS EXP", tt.result, " \n THIS IS ACT", res)
+		}
+	}
+}
@@ -0,0 +1,82 @@
+package run
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strings"
+
+	"go.keploy.io/server/pkg/jsonpath"
+	"go.keploy.io/server/pkg/models"
+)
+
+// normalizeFields returns the recorded response with only the given fields replaced by the ones
+// from the actual response. Fields are "status_code", "header", "header.<name>", "body",
+// dot-delimited body keys like "body.user.id" or JSONPath expressions on the body. The whole
+// response is replaced if no fields are given.
+func normalizeFields(exp, act models.HttpResp, fields []string) (models.HttpResp, error) {
+	if len(fields) == 0 {
+		return act, nil
+	}
+	res := models.HttpResp{
+		StatusCode: exp.StatusCode,
+		Header:     exp.Header.Clone(),
+		Body:       exp.Body,
+	}
+	var paths []jsonpath.Path
+	for _, f := range fields {
+		switch {
+		case f == "status_code":
+			res.StatusCode = act.StatusCode
+		case f == "header":
+			res.Header = act.Header.Clone()
+		case strings.HasPrefix(f, "header."):
+			k := http.CanonicalHeaderKey(strings.TrimPrefix(f, "header."))
+			if res.Header == nil {
+				res.Header = http.Header{}
+			}
+			if v, ok := act.Header[k]; ok {
+				res.Header[k] = v
+			} else {
+				delete(res.Header, k)
+			}
+		case f == "body":
+			res.Body = act.Body
+		case strings.HasPrefix(f, "body.") || jsonpath.IsPath(f):
+			if !jsonpath.IsPath(f) {
+				f = "$" + strings.TrimPrefix(f, "body")
+			}
+			p, err := jsonpath.Parse(f)
+			if err != nil {
+				return exp, err
+			}
+			paths = append(paths, p)
+		default:
+			return exp, fmt.Errorf("unknown field %q", f)
+		}
+	}
+	if len(paths) == 0 {
+		return res, nil
+	}
+
+	var expBody, actBody interface{}
+	if err := json.Unmarshal([]byte(res.Body), &expBody); err != nil {
+		return exp, fmt.Errorf("recorded body is not a valid json: %v", err)
+	}
+	if err := json.Unmarshal([]byte(act.Body), &actBody); err != nil {
+		return exp, fmt.Errorf("actual body is not a valid json: %v", err)
+	}
+	for _, p := range paths {
+		b, err := jsonpath.Copy(expBody, actBody, p)
+		if err != nil {
+			return exp, err
+		}
+		expBody = b
+	}
+	b, err := json.Marshal(expBody)
+	if err != nil {
+		return exp, err
+	}
+	res.Body = string(b)
+	return res, nil
+}
@@ -30,26 +30,40 @@ type Run struct {
 	log      *zap.Logger
 }
 
-func (r *Run) Normalize(ctx context.Context, cid, id string) error {
+func (r *Run) Normalize(ctx context.Context, cid, id string, fields []string, dryRun bool) (NormalizeResult, error) {
 	t, err := r.rdb.ReadTest(ctx, id)
 	if err != nil {
 		r.log.Error("failed to fetch test from db", zap.String("cid", cid), zap.String("id", id), zap.Error(err))
-		return errors.New("test not found")
+		return NormalizeResult{}, errors.New("test not found")
 	}
 	tc, err := r.tdb.Get(ctx, cid, t.TestCaseID)
 	if err != nil {
 		r.log.Error("failed to fetch testcase from db", zap.String("cid", cid), zap.String("id", id), zap.Error(err))
-		return errors.New("testcase not found")
+		return NormalizeResult{}, errors.New("testcase not found")
 	}
 	// update the responses
-	tc.HttpResp = t.Resp
+	resp, err := normalizeFields(tc.HttpResp, t.Resp, fields)
+	if err != nil {
+		r.log.Error("failed to normalize the testcase fields", zap.String("cid", cid), zap.String("id", id), zap.Strings("fields", fields), zap.Error(err))
+		return NormalizeResult{}, err
+	}
+	res := NormalizeResult{
+		TestID:     id,
+		TestCaseID: tc.ID,
+		Before:     tc.HttpResp,
+		After:      resp,
+	}
+	if dryRun {
+		return res, nil
+	}
+	tc.HttpResp = resp
 	err = r.tdb.Upsert(ctx, tc)
 	if err != nil {
 		r.log.Error("failed to update testcase in db", zap.String("cid", cid), zap.String("id", id), zap.Error(err))
-		return errors.New("could not update testcase")
+		return NormalizeResult{}, errors.New("could not update testcase")
 	}
 	r.tele.Normalize(r.client, ctx)
-	return nil
+	return res, nil
 }
 
 func (r *Run) Get(ctx context.Context, summary bool, cid string, user, app, id *string, from, to *time.Time, offset *int, limit *int) ([]*TestRun, error) {
@@ -10,7 +10,10 @@ import (
 type Service interface {
 	Get(ctx context.Context, summary bool, cid string, user, app, id *string, from, to *time.Time, offset *int, limit *int) ([]*TestRun, error)
 	Put(ctx context.Context, run TestRun) error
-	Normalize(ctx context.Context, cid, id string) error
+	// Normalize updates the recorded response of the testcase with the given fields of the
+	// response from the test. With dryRun the testcase is only returned and not updated.
+	Normalize(ctx context.Context, cid, id string, fields []string, dryRun bool) (NormalizeResult, error)
+	Drift(ctx context.Context, cid, runID string) ([]EndpointDrift, error)
 }
 
 type DB interface {
@@ -22,6 +25,14 @@ type DB interface {
 	Increment(ctx context.Context, success, failure bool, id string) error
 }
 
+// NormalizeResult is the recorded response of a testcase before and after normalizing it.
+type NormalizeResult struct {
+	TestID     string          `json:"test_id"`
+	TestCaseID string          `json:"test_case_id"`
+	Before     models.HttpResp `json:"before"`
+	After      models.HttpResp `json:"after"`
+}
+
 type TestRun struct {
 	ID      string        `json:"id" bson:"_id"`
 	Created int64         `json:"created" bson:"created,omitempty"`
@@ -34,6 +45,19 @@ type TestRun struct {
 	Failure int           `json:"failure" bson:"failure,omitempty"`
 	Total   int           `json:"total" bson:"total,omitempty"`
 	Tests   []Test        `json:"tests" bson:"-"`
+
+	// Latency is only measured when the testcases are replayed against a remote deployment.
+	Latency []EndpointLatency `json:"latency,omitempty" bson:"latency,omitempty"`
+}
+
+// EndpointLatency is the response time of an endpoint, in milliseconds.
+type EndpointLatency struct {
+	Method models.Method `json:"method" bson:"method"`
+	URI    string        `json:"uri" bson:"uri"`
+	Count  int           `json:"count" bson:"count"`
+	P50    int64         `json:"p50" bson:"p50"`
+	P95    int64         `json:"p95" bson:"p95"`
+	Max    int64         `json:"max" bson:"max"`
 }
 
 type TestRunStatus string
@@ -57,6 +81,7 @@ type Test struct {
 	Resp       models.HttpResp     `json:"http_resp" bson:"http_resp,omitempty"`
 	Noise      []string            `json:"noise" bson:"noise"`
 	Result     Result              `json:"result" bson:"result"`
+	Stale      bool                `json:"stale" bson:"stale,omitempty"`
 }
 
 type Result struct {
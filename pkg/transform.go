@@ -0,0 +1,139 @@
+package pkg
+
+import (
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+
+	"go.keploy.io/server/pkg/jsonpath"
+	"go.keploy.io/server/pkg/models"
+)
+
+// ValidateTransforms returns an error for the first invalid transformation rule.
+func ValidateTransforms(rules []models.Transform) error {
+	for _, t := range rules {
+		switch t.Type {
+		case models.TransformSort, models.TransformUnwrap, models.TransformLowercase:
+		default:
+			return fmt.Errorf("invalid transform %q on %q: unknown type", t.Type, t.Field)
+		}
+		if strings.HasPrefix(t.Field, "header.") {
+			if t.Type != models.TransformLowercase {
+				return fmt.Errorf("invalid transform %q on %q: only LOWERCASE can be applied to headers", t.Type, t.Field)
+			}
+			if t.Field == "header." {
+				return fmt.Errorf("invalid transform %q: missing header name", t.Type)
+			}
+			continue
+		}
+		if _, err := jsonpath.Parse(t.Field); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// Transform returns a copy of the response with the transformation rules applied. Body rules
+// are ignored for responses which don't have a JSON body.
+func Transform(resp models.HttpResp, rules []models.Transform) (models.HttpResp, error) {
+	if err := ValidateTransforms(rules); err != nil {
+		return resp, err
+	}
+	res := models.HttpResp{
+		StatusCode: resp.StatusCode,
+		Header:     resp.Header.Clone(),
+		Body:       resp.Body,
+	}
+
+	var body interface{}
+	isJSON := json.Valid([]byte(resp.Body))
+	if isJSON {
+		if err := json.Unmarshal([]byte(resp.Body), &body); err != nil {
+			return resp, err
+		}
+	}
+	changed := false
+	for _, t := range rules {
+		if strings.HasPrefix(t.Field, "header.") {
+			name := strings.TrimPrefix(t.Field, "header.")
+			for k, v := range res.Header {
+				if !strings.EqualFold(k, name) {
+					continue
+				}
+				values := make([]string, len(v))
+				for i, s := range v {
+					values[i] = strings.ToLower(s)
+				}
+				res.Header[k] = values
+			}
+			continue
+		}
+		if !isJSON {
+			continue
+		}
+		path, _ := jsonpath.Parse(t.Field)
+		switch t.Type {
+		case models.TransformSort:
+			key := t.Key
+			body = jsonpath.Update(body, path, func(v interface{}) interface{} {
+				return sortArray(v, key)
+			})
+		case models.TransformLowercase:
+			body = jsonpath.Update(body, path, func(v interface{}) interface{} {
+				if s, ok := v.(string); ok {
+					return strings.ToLower(s)
+				}
+				return v
+			})
+		case models.TransformUnwrap:
+			var inner interface{}
+			found := false
+			jsonpath.Update(body, path, func(v interface{}) interface{} {
+				if !found {
+					inner, found = v, true
+				}
+				return v
+			})
+			if found {
+				body = inner
+			}
+		}
+		changed = true
+	}
+	if changed {
+		b, err := json.Marshal(body)
+		if err != nil {
+			return resp, err
+		}
+		res.Body = string(b)
+	}
+	return res, nil
+}
+
+// sortArray returns a sorted copy of the given JSON array. Objects are compared by the value
+// of key, other elements by their own value.
+func sortArray(v interface{}, key string) interface{} {
+	arr, ok := v.([]interface{})
+	if !ok {
+		return v
+	}
+	sortKey := func(e interface{}) interface{} {
+		if m, ok := e.(map[string]interface{}); ok && key != "" {
+			return m[key]
+		}
+		return e
+	}
+	res := make([]interface{}, len(arr))
+	copy(res, arr)
+	sort.SliceStable(res, func(i, j int) bool {
+		a, b := sortKey(res[i]), sortKey(res[j])
+		if x, ok := a.(float64); ok {
+			if y, ok := b.(float64); ok {
+				return x < y
+			}
+		}
+		return fmt.Sprint(a) < fmt.Sprint(b)
+	})
+	return res
+}
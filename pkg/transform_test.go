@@ -0,0 +1,95 @@
+package pkg
+
+import (
+	"net/http"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+	"go.keploy.io/server/pkg/service/run"
+	"go.uber.org/zap"
+)
+
+func TestTransform(t *testing.T) {
+	for _, tt := range []struct {
+		exp    models.HttpResp
+		actual models.HttpResp
+		rules  []models.Transform
+		result bool
+	}{
+		// sort an array of objects by key
+		{
+			exp:    models.HttpResp{Body: `{"users": [{"id": 1}, {"id": 2}], "total": 2}`},
+			actual: models.HttpResp{Body: `{"users": [{"id": 2}, {"id": 1}], "total": 2}`},
+			rules:  []models.Transform{{Type: models.TransformSort, Field: "$.users", Key: "id"}},
+			result: true,
+		},
+		// strip the wrapper field
+		{
+			exp:    models.HttpResp{Body: `{"data": {"name": "keploy"}, "request_id": "abc"}`},
+			actual: models.HttpResp{Body: `{"data": {"name": "keploy"}, "request_id": "xyz"}`},
+			rules:  []models.Transform{{Type: models.TransformUnwrap, Field: "$.data"}},
+			result: true,
+		},
+		{
+			exp:    models.HttpResp{Body: `{"data": {"name": "keploy"}}`},
+			actual: models.HttpResp{Body: `{"data": {"name": "other"}}`},
+			rules:  []models.Transform{{Type: models.TransformUnwrap, Field: "$.data"}},
+			result: false,
+		},
+		// lowercase a header and a body field
+		{
+			exp: models.HttpResp{
+				Header: http.Header{"Content-Type": {"application/JSON"}},
+				Body:   `{"status": "OK"}`,
+			},
+			actual: models.HttpResp{
+				Header: http.Header{"Content-Type": {"application/json"}},
+				Body:   `{"status": "ok"}`,
+			},
+			rules: []models.Transform{
+				{Type: models.TransformLowercase, Field: "header.content-type"},
+				{Type: models.TransformLowercase, Field: "$.status"},
+			},
+			result: true,
+		},
+	} {
+		logger, _ := zap.NewProduction()
+		defer logger.Sync()
+		exp, err := Transform(tt.exp, tt.rules)
+		if err != nil {
+			t.Fatal(err)
+		}
+		act, err := Transform(tt.actual, tt.rules)
+		if err != nil {
+			t.Fatal(err)
+		}
+		res, err := Match(exp.Body, act.Body, nil, logger)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if res && tt.exp.Header != nil {
+			res = CompareHeaders(exp.Header, act.Header, &[]run.HeaderResult{})
+		}
+		if res != tt.result {
+			t.Fatal(tt.exp, tt.actual, "THIS IS EXP", tt.result, " \n THIS IS ACT", res)
+		}
+	}
+}
+
+func TestValidateTransforms(t *testing.T) {
+	for _, tt := range []struct {
+		rules []models.Transform
+		valid bool
+	}{
+		{rules: []models.Transform{{Type: models.TransformSort, Field: "$.items", Key: "id"}}, valid: true},
+		{rules: []models.Transform{{Type: models.TransformLowercase, Field: "header.Location"}}, valid: true},
+		{rules: []models.Transform{{Type: models.TransformSort, Field: "header.Location"}}, valid: false},
+		{rules: []models.Transform{{Type: models.TransformUnwrap, Field: "data"}}, valid: false},
+		{rules: []models.Transform{{Type: "REVERSE", Field: "$.items"}}, valid: false},
+	} {
+		err := ValidateTransforms(tt.rules)
+		if (err == nil) != tt.valid {
+			t.Fatal(tt.rules, "expected valid:", tt.valid, "got error:", err)
+		}
+	}
+}
@@ -2,6 +2,9 @@ package pkg
 
 import (
 	"net/http"
+	"strconv"
+	"strings"
+	"time"
 
 	"go.keploy.io/server/pkg/service/run"
 )
@@ -130,3 +133,14 @@ func Contains(elems []string, v string) bool {
 	}
 	return false
 }
+
+// ParseDuration is time.ParseDuration which also accepts a number of days, eg: "90d".
+func ParseDuration(s string) (time.Duration, error) {
+	if strings.HasSuffix(s, "d") {
+		days, err := strconv.Atoi(strings.TrimSuffix(s, "d"))
+		if err == nil {
+			return time.Duration(days) * 24 * time.Hour, nil
+		}
+	}
+	return time.ParseDuration(s)
+}
@@ -6,8 +6,8 @@ import (
 	"fmt"
 	"net/http"
 	"testing"
+	"time"
 
-	// "time"
 	"github.com/go-test/deep"
 	"go.keploy.io/server/pkg/service/run"
 	"go.uber.org/zap"
@@ -258,3 +258,22 @@ func isEqual(x, y []run.HeaderResult) []string {
 
 	return deep.Equal(expected, actual)
 }
+
+func TestParseDuration(t *testing.T) {
+	for _, tt := range []struct {
+		s   string
+		d   time.Duration
+		err bool
+	}{
+		{s: "90d", d: 90 * 24 * time.Hour},
+		{s: "36h", d: 36 * time.Hour},
+		{s: "1h30m", d: 90 * time.Minute},
+		{s: "d", err: true},
+		{s: "ninety", err: true},
+	} {
+		d, err := ParseDuration(tt.s)
+		if (err != nil) != tt.err || d != tt.d {
+			t.Fatal(tt.s, "THIS IS EXP", tt.d, " \n THIS IS ACT", d, err)
+		}
+	}
+}
@@ -0,0 +1,60 @@
+package pkg
+
+import (
+	"errors"
+	"fmt"
+	"net/url"
+	"strings"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+var methods = []models.Method{
+	models.MethodGet,
+	models.MethodPut,
+	models.MethodHead,
+	models.MethodPost,
+	models.MethodPatch,
+	models.MethodDelete,
+	models.MethodOptions,
+	models.MethodTrace,
+}
+
+// ValidateTestCase returns an error for the first invalid field of a hand-edited testcase, so
+// that mistakes are reported when the testcase is saved instead of failing the tests later.
+func ValidateTestCase(tc models.TestCase) error {
+	if tc.ID == "" {
+		return errors.New("invalid testcase: missing id")
+	}
+	valid := false
+	for _, m := range methods {
+		if strings.EqualFold(string(tc.HttpReq.Method), string(m)) {
+			valid = true
+			break
+		}
+	}
+	if !valid {
+		return fmt.Errorf("invalid testcase %v: unknown http method %q", tc.ID, tc.HttpReq.Method)
+	}
+	if _, err := url.Parse(tc.HttpReq.URL); err != nil {
+		return fmt.Errorf("invalid testcase %v: %w", tc.ID, err)
+	}
+	if tc.HttpResp.StatusCode < 100 || tc.HttpResp.StatusCode > 599 {
+		return fmt.Errorf("invalid testcase %v: status code %d is out of range", tc.ID, tc.HttpResp.StatusCode)
+	}
+	for k := range tc.HttpResp.Header {
+		if strings.TrimSpace(k) == "" {
+			return fmt.Errorf("invalid testcase %v: empty response header name", tc.ID)
+		}
+	}
+	if err := ValidateNoise(tc.Noise); err != nil {
+		return fmt.Errorf("invalid testcase %v: %w", tc.ID, err)
+	}
+	if err := ValidateTransforms(tc.Transforms); err != nil {
+		return fmt.Errorf("invalid testcase %v: %w", tc.ID, err)
+	}
+	if err := ValidateHeaderAssertions(tc.HeaderAssertions); err != nil {
+		return fmt.Errorf("invalid testcase %v: %w", tc.ID, err)
+	}
+	return nil
+}
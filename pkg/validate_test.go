@@ -0,0 +1,45 @@
+package pkg
+
+import (
+	"net/http"
+	"testing"
+
+	"go.keploy.io/server/pkg/models"
+)
+
+func TestValidateTestCase(t *testing.T) {
+	valid := models.TestCase{
+		ID:       "1",
+		HttpReq:  models.HttpReq{Method: models.MethodPost, URL: "/users?page=1"},
+		HttpResp: models.HttpResp{StatusCode: 201, Header: http.Header{"Content-Type": {"application/json"}}},
+		Noise:    []string{"body.id", "$.items[*].ts"},
+	}
+	for _, tt := range []struct {
+		edit  func(tc *models.TestCase)
+		valid bool
+	}{
+		{edit: func(tc *models.TestCase) {}, valid: true},
+		{edit: func(tc *models.TestCase) { tc.HttpReq.Method = "get" }, valid: true},
+		{edit: func(tc *models.TestCase) { tc.ID = "" }, valid: false},
+		{edit: func(tc *models.TestCase) { tc.HttpReq.Method = "FETCH" }, valid: false},
+		{edit: func(tc *models.TestCase) { tc.HttpReq.URL = "%zz" }, valid: false},
+		// a missing http_resp in the edited testcase
+		{edit: func(tc *models.TestCase) { tc.HttpResp = models.HttpResp{} }, valid: false},
+		{edit: func(tc *models.TestCase) { tc.Noise = []string{"$.items["} }, valid: false},
+		{edit: func(tc *models.TestCase) {
+			tc.Transforms = []models.Transform{{Type: models.TransformSort, Field: "header.Accept"}}
+		}, valid: false},
+		{edit: func(tc *models.TestCase) {
+			tc.HeaderAssertions = []models.HeaderAssertion{{Header: "Location", Rule: models.AssertRegex, Value: "^/users/"}}
+		}, valid: true},
+		{edit: func(tc *models.TestCase) {
+			tc.HeaderAssertions = []models.HeaderAssertion{{Header: "Location", Rule: "MATCHES"}}
+		}, valid: false},
+	} {
+		tc := valid
+		tt.edit(&tc)
+		if err := ValidateTestCase(tc); (err == nil) != tt.valid {
+			t.Fatal(tc, "THIS IS EXP", tt.valid, " \n THIS IS ACT", err)
+		}
+	}
+}
@@ -1,8 +1,10 @@
 package server
 
 import (
+	"context"
 	"math/rand"
 	"net/http"
+	"sync"
 	"time"
 
 	// "log"
@@ -22,6 +24,8 @@ import (
 	"go.keploy.io/server/graph"
 	"go.keploy.io/server/graph/generated"
 	"go.keploy.io/server/http/regression"
+	"go.keploy.io/server/pkg"
+	"go.keploy.io/server/pkg/models"
 	"go.keploy.io/server/pkg/platform/mgo"
 	"go.keploy.io/server/pkg/platform/telemetry"
 	regression2 "go.keploy.io/server/pkg/service/regression"
@@ -34,30 +38,49 @@ import (
 // const defaultPort = "8080"
 
 type config struct {
-	MongoURI        string `envconfig:"MONGO_URI" default:"mongodb://localhost:27017"`
-	DB              string `envconfig:"DB" default:"keploy"`
-	TestCaseTable   string `envconfig:"TEST_CASE_TABLE" default:"test-cases"`
-	TestRunTable    string `envconfig:"TEST_RUN_TABLE" default:"test-runs"`
-	TestTable       string `envconfig:"TEST_TABLE" default:"tests"`
-	TelemetryTable  string `envconfig:"TELEMETRY_TABLE" default:"telemetry"`
-	APIKey          string `envconfig:"API_KEY"`
-	EnableDeDup     bool   `envconfig:"ENABLE_DEDUP" default:"false"`
-	EnableTelemetry bool   `envconfig:"ENABLE_TELEMETRY" default:"true"`
+	MongoURI            string   `envconfig:"MONGO_URI" default:"mongodb://localhost:27017"`
+	DB                  string   `envconfig:"DB" default:"keploy"`
+	TestCaseTable       string   `envconfig:"TEST_CASE_TABLE" default:"test-cases"`
+	TestRunTable        string   `envconfig:"TEST_RUN_TABLE" default:"test-runs"`
+	TestTable           string   `envconfig:"TEST_TABLE" default:"tests"`
+	TelemetryTable      string   `envconfig:"TELEMETRY_TABLE" default:"telemetry"`
+	APIKey              string   `envconfig:"API_KEY"`
+	EnableDeDup         bool     `envconfig:"ENABLE_DEDUP" default:"false"`
+	EnableTelemetry     bool     `envconfig:"ENABLE_TELEMETRY" default:"true"`
+	NormalizeIDs        bool     `envconfig:"NORMALIZE_IDS" default:"false"`
+	NormalizeCookies    bool     `envconfig:"NORMALIZE_COOKIES" default:"false"`
+	RecordIncludePaths  []string `envconfig:"RECORD_INCLUDE_PATHS"`
+	RecordExcludePaths  []string `envconfig:"RECORD_EXCLUDE_PATHS"`
+	RecordMethods       []string `envconfig:"RECORD_METHODS"`
+	RecordExcludeStatus []string `envconfig:"RECORD_EXCLUDE_STATUS"`
+	MockMaxAge          string   `envconfig:"MOCK_MAX_AGE"`
+	FailOnStale         bool     `envconfig:"FAIL_ON_STALE" default:"false"`
+	ReplayBaseURL       string   `envconfig:"REPLAY_BASE_URL"`
+	ReplayApps          []string `envconfig:"REPLAY_APPS"`
+	ReplayInterval      string   `envconfig:"REPLAY_INTERVAL" default:"24h"`
+	ReplayConcurrency   int      `envconfig:"REPLAY_CONCURRENCY" default:"1"`
+	ReplayTokenURL      string   `envconfig:"REPLAY_TOKEN_URL"`
+	ReplayTokenBody     string   `envconfig:"REPLAY_TOKEN_BODY"`
+	ReplayTokenField    string   `envconfig:"REPLAY_TOKEN_FIELD"`
+	LogFormat           string   `envconfig:"LOG_FORMAT" default:"console"`
 }
 
-func Server() *chi.Mux {
+// Server returns the router of the keploy server. The background work it starts, eg: scheduled
+// replays, is added to wg and stops when ctx is cancelled.
+func Server(ctx context.Context, wg *sync.WaitGroup) *chi.Mux {
 	rand.Seed(time.Now().UTC().UnixNano())
 
-	logger, err := zap.NewDevelopment()
+	var conf config
+	confErr := envconfig.Process("keploy", &conf)
+
+	logger, err := newLogger(conf.LogFormat)
 	if err != nil {
 		panic(err)
 	}
 	defer logger.Sync() // flushes buffer, if any
 
-	var conf config
-	err = envconfig.Process("keploy", &conf)
-	if err != nil {
-		logger.Error("failed to read/process configuration", zap.Error(err))
+	if confErr != nil {
+		logger.Error("failed to read/process configuration", zap.Error(confErr))
 	}
 
 	cl, err := mgo.New(conf.MongoURI)
@@ -77,9 +100,54 @@ func Server() *chi.Mux {
 	client := http.Client{
 		Transport: khttpclient.NewInterceptor(http.DefaultTransport),
 	}
-	regSrv := regression2.New(tdb, rdb, logger, conf.EnableDeDup, analyticsConfig, client)
+	var methods []models.Method
+	for _, m := range conf.RecordMethods {
+		methods = append(methods, models.Method(m))
+	}
+	filter, err := pkg.NewRecordFilter(models.Filter{
+		IncludePaths:  conf.RecordIncludePaths,
+		ExcludePaths:  conf.RecordExcludePaths,
+		Methods:       methods,
+		ExcludeStatus: conf.RecordExcludeStatus,
+	})
+	if err != nil {
+		logger.Fatal("failed to parse the record filters", zap.Error(err))
+	}
+
+	var maxAge time.Duration
+	if conf.MockMaxAge != "" {
+		maxAge, err = pkg.ParseDuration(conf.MockMaxAge)
+		if err != nil {
+			logger.Fatal("failed to parse the max age of mocks", zap.String("max age", conf.MockMaxAge), zap.Error(err))
+		}
+	}
+
+	regSrv := regression2.New(tdb, rdb, logger, regression2.Config{
+		EnableDeDup:      conf.EnableDeDup,
+		NormalizeIDs:     conf.NormalizeIDs,
+		NormalizeCookies: conf.NormalizeCookies,
+		Filter:           filter,
+		MaxAge:           maxAge,
+		FailOnStale:      conf.FailOnStale,
+	}, analyticsConfig, client)
 	runSrv := run.New(rdb, tdb, logger, analyticsConfig, client)
 
+	if conf.ReplayBaseURL != "" {
+		interval, err := pkg.ParseDuration(conf.ReplayInterval)
+		if err != nil || interval <= 0 {
+			logger.Fatal("failed to parse the replay interval", zap.String("interval", conf.ReplayInterval), zap.Error(err))
+		}
+		var auth *regression2.TokenAuth
+		if conf.ReplayTokenURL != "" {
+			auth = &regression2.TokenAuth{URL: conf.ReplayTokenURL, Body: conf.ReplayTokenBody, Field: conf.ReplayTokenField}
+		}
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			scheduleReplay(ctx, logger, regSrv, runSrv, conf.ReplayApps, conf.ReplayBaseURL, interval, conf.ReplayConcurrency, auth)
+		}()
+	}
+
 	srv := handler.NewDefaultServer(generated.NewExecutableSchema(generated.Config{Resolvers: graph.NewResolver(logger, runSrv, regSrv)}))
 
 	// initialize the client serveri
@@ -128,3 +196,70 @@ func Server() *chi.Mux {
 	analyticsConfig.Ping(keploy.GetMode() == keploy.MODE_TEST)
 	return r
 }
+
+// newLogger returns a logger which writes human readable logs, or with the "json" format one
+// JSON object per line on stdout for tools which consume the events of the server.
+func newLogger(format string) (*zap.Logger, error) {
+	if format != "json" {
+		return zap.NewDevelopment()
+	}
+	cfg := zap.NewProductionConfig()
+	// errors of the logger itself stay on stderr, with the github annotations
+	cfg.OutputPaths = []string{"stdout"}
+	// every event is needed by the tools consuming them, eg: to count the failed tests
+	cfg.Sampling = nil
+	return cfg.Build()
+}
+
+// scheduleReplay periodically replays the testcases of the given apps against the deployment at
+// baseURL and reports the endpoints whose responses changed, so that outdated testcases are
+// noticed before they fail in CI. All the apps with recorded testcases are replayed when no
+// apps are given. Changes are only logged: the recorded testcases and their mocks are not
+// updated. It returns when ctx is cancelled.
+func scheduleReplay(ctx context.Context, logger *zap.Logger, regSrv regression2.Service, runSrv run.Service, apps []string, baseURL string, interval time.Duration, concurrency int, auth *regression2.TokenAuth) {
+	ticker := time.NewTicker(interval)
+	defer ticker.Stop()
+	wait := func() {
+		select {
+		case <-ctx.Done():
+		case <-ticker.C:
+		}
+	}
+	for ; ctx.Err() == nil; wait() {
+		replayApps := apps
+		if len(replayApps) == 0 {
+			var err error
+			replayApps, err = regSrv.GetApps(ctx, graph.DEFAULT_COMPANY)
+			if err != nil {
+				logger.Error("failed to get the apps to replay", zap.Error(err))
+				continue
+			}
+			if len(replayApps) == 0 {
+				logger.Warn("no apps with recorded testcases to replay", zap.String("base url", baseURL))
+			}
+		}
+		for _, app := range replayApps {
+			if ctx.Err() != nil {
+				return
+			}
+			tr, err := regSrv.Replay(ctx, graph.DEFAULT_COMPANY, graph.DEFAULT_USER, app, baseURL, concurrency, auth)
+			if err != nil {
+				logger.Error("failed to replay the testcases", zap.String("app", app), zap.String("base url", baseURL), zap.Error(err))
+				continue
+			}
+			if tr.Status == run.TestRunStatusPassed {
+				logger.Info("replayed the testcases", zap.String("app", app), zap.String("test run", tr.ID), zap.Int("total", tr.Total))
+				continue
+			}
+			drift, err := runSrv.Drift(ctx, graph.DEFAULT_COMPANY, tr.ID)
+			if err != nil {
+				logger.Error("failed to compute the drift of the test run", zap.String("test run", tr.ID), zap.Error(err))
+				continue
+			}
+			for _, d := range drift {
+				logger.Warn("responses changed", zap.String("app", app), zap.String("test run", tr.ID), zap.String("method", string(d.Method)), zap.String("uri", d.URI),
+					zap.Strings("added fields", d.AddedFields), zap.Strings("removed fields", d.RemovedFields), zap.Int("type changes", len(d.TypeChanges)), zap.Int("status changes", len(d.StatusChanges)))
+			}
+		}
+	}
+}
@@ -0,0 +1,50 @@
+package server
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"testing"
+
+	"go.uber.org/zap"
+)
+
+func TestJSONLogger(t *testing.T) {
+	r, w, err := os.Pipe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	stdout := os.Stdout
+	os.Stdout = w
+	logger, err := newLogger("json")
+	os.Stdout = stdout
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// more events with the same message than zap samples by default
+	const events = 300
+	go func() {
+		for i := 0; i < events; i++ {
+			logger.Info("test passed", zap.String("testcase", "1"), zap.Int("i", i))
+		}
+		logger.Sync()
+		w.Close()
+	}()
+
+	n := 0
+	sc := bufio.NewScanner(r)
+	for sc.Scan() {
+		var event map[string]interface{}
+		if err := json.Unmarshal(sc.Bytes(), &event); err != nil {
+			t.Fatal("expected a JSON object per line, got", sc.Text(), err)
+		}
+		if event["msg"] != "test passed" || event["testcase"] != "1" || event["i"] != float64(n) {
+			t.Fatal("THIS IS EXP", n, " \n THIS IS ACT", event)
+		}
+		n++
+	}
+	if n != events {
+		t.Fatal("THIS IS EXP", events, " \n THIS IS ACT", n)
+	}
+}